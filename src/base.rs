@@ -1,13 +1,15 @@
-use std::{ops::{Div, Mul}, fmt::Debug};
-use typenum::op;
+use std::{marker::PhantomData, ops::{Div, Mul}, fmt::Debug};
+use typenum::{op, Diff, Negate, Sum};
 
 pub use crate::{BaseUnit, Unit, Value};
+use crate::dimension::{Dimension, Dimensionless};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Unitless;
 
 impl Unit for Unitless {
-    type Base = Self;
+    type Base = Coherent<Dimensionless>;
+    type Dimension = Dimensionless;
     fn create() -> Self {
         Self
     }
@@ -36,11 +38,16 @@ impl Unitless {
 }
 
 macro_rules! base_unit {
-    ($name:ident : $($ty:ident),*) => {
+    ($name:ident : $ty:ident) => {
+        base_unit!($name: $ty, 0);
+    };
+    ($name:ident : $ty:ident, $scale:literal) => {
         #[derive(Clone, Copy, PartialEq, Eq)]
         pub struct $name;
         impl Unit for $name {
-            type Base = Self;
+            type Base = Coherent<Self::Dimension>;
+            type Dimension = crate::dimension::$ty;
+            const SCALE: i32 = $scale;
             fn create() -> Self {
                 Self
             }
@@ -52,10 +59,6 @@ macro_rules! base_unit {
 
         impl BaseUnit for $name {}
 
-        $(impl crate::$ty for $name {
-            type TypedBase = Self::Base;
-        })*
-
         impl<U: BaseUnit> Mul<U> for $name {
             type Output = Mult<$name, U>;
             fn mul(self, rhs: U) -> Self::Output {
@@ -88,6 +91,7 @@ macro_rules! base_unit {
             where Self: Mul<U>
         {
             type Output = op!(Self * U);
+            #[allow(clippy::suspicious_arithmetic_impl)]
             fn div(self, rhs: Inverse<U>) -> Self::Output {
                 self * rhs.0
             }
@@ -109,7 +113,8 @@ macro_rules! base_unit {
 
 base_unit!(Second: Time);
 base_unit!(Meter: Length);
-base_unit!(Gram: Mass);
+// The coherent SI unit of mass is the kilogram, so a gram is a milli-kilogram
+base_unit!(Gram: Mass, 3);
 base_unit!(Ampere: Current);
 base_unit!(Kelvin: Tempature);
 base_unit!(Mole: Amount);
@@ -123,12 +128,21 @@ pub trait Invert: Unit {
 // represents 1/U
 #[derive(Clone, Copy)]
 pub struct Inverse<U: BaseUnit>(U);
-impl<U: BaseUnit> Unit for Inverse<U> {
-    type Base = U;
+impl<U: BaseUnit> Unit for Inverse<U>
+where
+    U::Dimension: std::ops::Neg,
+{
+    type Base = Coherent<Self::Dimension>;
+    type Dimension = Negate<U::Dimension>;
+    const SCALE: i32 = -U::SCALE;
 
     fn create() -> Self {
         Self(U::create())
     }
+
+    fn factor() -> f64 {
+        1.0 / U::factor()
+    }
 }
 
 impl<U: BaseUnit + Debug> Debug for Inverse<U> {
@@ -137,14 +151,20 @@ impl<U: BaseUnit + Debug> Debug for Inverse<U> {
     }
 }
 
-impl<U: BaseUnit> Invert for Inverse<U> {
+impl<U: BaseUnit> Invert for Inverse<U>
+where
+    Self: Unit,
+{
     type Inverse = U;
     fn invert(self) -> Self::Inverse {
         self.0
     }
 }
 
-impl<U: BaseUnit> Invert for U {
+impl<U: BaseUnit> Invert for U
+where
+    Inverse<U>: Unit,
+{
     type Inverse = Inverse<U>;
     fn invert(self) -> Self::Inverse {
         Inverse(self)
@@ -165,11 +185,19 @@ where
 #[derive(Clone, Copy)]
 pub struct Mult<U, V>(U, V);
 
-impl<U: Unit, V: Unit> Unit for Mult<U, V> {
-    type Base = Mult<U::Base, V::Base>;
+impl<U: Unit, V: Unit> Unit for Mult<U, V>
+where
+    U::Dimension: std::ops::Add<V::Dimension>,
+{
+    type Base = Coherent<Self::Dimension>;
+    type Dimension = Sum<U::Dimension, V::Dimension>;
+    const SCALE: i32 = U::SCALE + V::SCALE;
     fn create() -> Self {
         Self(U::create(), V::create())
     }
+    fn factor() -> f64 {
+        U::factor() * V::factor()
+    }
 }
 
 impl<U: Debug, V: Debug> Debug for Mult<U, V> {
@@ -178,7 +206,11 @@ impl<U: Debug, V: Debug> Debug for Mult<U, V> {
     }
 }
 
-impl<U: Unit + Invert, V: Unit + Invert> Invert for Mult<U, V> {
+impl<U: Unit + Invert, V: Unit + Invert> Invert for Mult<U, V>
+where
+    Self: Unit,
+    Mult<U::Inverse, V::Inverse>: Unit,
+{
     type Inverse = Mult<U::Inverse, V::Inverse>;
 
     fn invert(self) -> Self::Inverse {
@@ -206,6 +238,7 @@ where
     type Output = <Self as Mul<Inverse<W>>>::Output;
     // type Output = op!(Self * Inverse<W>); // I think this needs an alias (since typenum op! only
     // works on identifiers)
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: W) -> Self::Output {
         self * Inverse(rhs)
     }
@@ -263,9 +296,83 @@ impl<U: Unit, V: BaseUnit> Div<Unitless> for Mult<U, V> {
     }
 }
 
+/// The coherent SI unit of the dimension `D`: the product of the SI base units (m, s, kg, mol, A,
+/// K and cd), each raised to the matching exponent of `D`.
+///
+/// Every unit projects onto the `Coherent` unit of its dimension through `Unit::Base`, and this
+/// is what operations which can't keep the structure of a unit (such as multiplying two values)
+/// produce.
+pub struct Coherent<D>(PhantomData<D>);
+
+impl<D> Clone for Coherent<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<D> Copy for Coherent<D> {}
+
+impl<D> Unit for Coherent<D> {
+    type Base = Self;
+    type Dimension = D;
+    fn create() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D: Dimension> Debug for Coherent<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const NAMES: [&str; 7] = [
+            "Meters", "Seconds", "Kilograms", "Moles", "Amperes", "Kelvins", "Candelas",
+        ];
+        let mut first = true;
+        for (name, exp) in NAMES.iter().zip(D::exponents()) {
+            if exp.0 == 0 {
+                continue;
+            }
+            if !first {
+                write!(f, " * ")?;
+            }
+            first = false;
+            match exp {
+                (1, 1) => write!(f, "{name}")?,
+                (num, 1) => write!(f, "{name}^{num}")?,
+                (num, den) => write!(f, "{name}^{num}/{den}")?,
+            }
+        }
+        if first {
+            write!(f, "Unitless")?;
+        }
+        Ok(())
+    }
+}
+
+// Multiply: C * C => C, exponents add
+impl<D, E> Mul<Coherent<E>> for Coherent<D>
+where
+    D: std::ops::Add<E>,
+{
+    type Output = Coherent<Sum<D, E>>;
+    fn mul(self, _rhs: Coherent<E>) -> Self::Output {
+        Coherent::create()
+    }
+}
+
+// Divide: C / C => C, exponents subtract
+impl<D, E> Div<Coherent<E>> for Coherent<D>
+where
+    D: std::ops::Sub<E>,
+{
+    type Output = Coherent<Diff<D, E>>;
+    fn div(self, _rhs: Coherent<E>) -> Self::Output {
+        Coherent::create()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dimension::IntDim;
+    use typenum::{N1, P2, Z0};
     macro_rules! assert_has_type {
         ($val: expr => $ty:ty) => {
             (|_x: $ty| ())($val);
@@ -281,4 +388,34 @@ mod tests {
         assert_has_type!(c => Second);
         assert_has_type!(Unitless / Second => Inverse<Second>);
     }
+
+    #[test]
+    fn coherent() {
+        let c = Coherent::<crate::dimension::Area>::create() / Coherent::<crate::dimension::Time>::create();
+        assert_has_type!(c => Coherent<IntDim<P2, N1, Z0, Z0, Z0, Z0, Z0>>);
+        assert_eq!(format!("{:?}", c), "Meters^2 * Seconds^-1");
+        assert_eq!(format!("{:?}", Coherent::<Dimensionless>::create()), "Unitless");
+    }
+
+    #[test]
+    fn base_projection() {
+        use crate::{dimension, prefix::Kilo};
+
+        assert_has_type!(<Inverse<Second> as Unit>::Base::create() => Coherent<dimension::Frequency>);
+        assert_has_type!(<Mult<Meter, Inverse<Second>> as Unit>::Base::create() => Coherent<dimension::Speed>);
+        assert_has_type!(<Mult<Gram, Gram> as Unit>::Base::create() => Coherent<IntDim<Z0, Z0, P2, Z0, Z0, Z0, Z0>>);
+        assert_eq!(<Mult<Gram, Inverse<Second>> as Unit>::SCALE, 3);
+        assert_eq!(<Inverse<Gram> as Unit>::SCALE, -3);
+
+        let mass = Kilo::<Gram>::new(2f64);
+        assert_eq!(*mass.to_base().value(), 2.0);
+        let rate = Kilo::<Inverse<Second>>::new(2f64);
+        assert_eq!(*rate.to_base().value(), 2000.0);
+        let flow = Value::<f64, Z0, Mult<Gram, Inverse<Second>>>::new(5.0);
+        assert_eq!(*flow.to_base().value(), 0.005);
+        assert_eq!(*Value::<f64, Z0, Inverse<Gram>>::new(1.0).to_base().value(), 1000.0);
+        assert_eq!(*Value::<f64, Z0, Mult<Gram, Inverse<Second>>>::from_base(flow.to_base()).value(), 5.0);
+        assert_eq!(*(mass * mass).to_base().value(), 4.0);
+        assert_eq!(*(Gram::new(1) / Second::new(1)).to_base().value(), 0.001);
+    }
 }
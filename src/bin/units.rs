@@ -0,0 +1,241 @@
+//! A converter in the style of GNU units, built with `--features cli`.
+//!
+//! ```text
+//! $ units '60 mi/h' 'km/h'
+//!         * 96.56064
+//!         / 0.010356187
+//! $ units -d '3 kN*m'
+//!         = 3000 m^2 s^-2 kg
+//!         Length^2 Time^-2 Mass
+//! $ units
+//! You have: ...
+//! $ units --repl
+//! > v = 12 m/s
+//! > t = 3 min
+//! > d = v*t -> km
+//! d = 2.16 km
+//! ```
+
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    process,
+};
+
+use units::{
+    quantity::Quantity,
+    registry::{self, UnitRegistry},
+    session::Session,
+};
+
+const USAGE: &str = "\
+usage: units [-f FILE]... [HAVE [WANT]] | units -d EXPR | units --repl [SESSION]
+    -f FILE    use the units defined in FILE, in GNU units format, instead of the built in ones";
+const REPL_HELP: &str = "\
+    NAME = EXPR [-> UNIT]   assign a variable, optionally showing it in UNIT
+    EXPR [-> UNIT]          evaluate an expression
+    vars                    list the variables
+    save FILE, load FILE    write or replay the assignments of this session
+    quit";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+    if let Err(err) = load_units_files(&mut args) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+    let result = match args[..] {
+        [] => interactive(),
+        ["-r" | "--repl"] => repl(Session::new()),
+        ["-r" | "--repl", file] => load(Session::new(), file).and_then(repl),
+        ["-h" | "--help"] => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        ["-d", expr] | [expr] => define(expr),
+        [have, want] => convert(have, want),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+/// Removes the `-f FILE` options from `args` and makes the units in those files the global
+/// registry
+fn load_units_files(args: &mut Vec<&str>) -> Result<(), String> {
+    let mut registry: Option<UnitRegistry> = None;
+    while let Some(i) = args.iter().position(|arg| *arg == "-f") {
+        let file = *args.get(i + 1).ok_or(USAGE)?;
+        args.drain(i..i + 2);
+        registry
+            .get_or_insert_with(UnitRegistry::new)
+            .load_units_file(file)
+            .map_err(|errors| {
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })?;
+    }
+    if let Some(registry) = registry {
+        registry::set_global(registry);
+    }
+    Ok(())
+}
+
+fn parse(input: &str) -> Result<Quantity, String> {
+    input.parse().map_err(|err| format!("{}: {}", input, err))
+}
+
+/// Prints how `have` is written in coherent SI units and which base quantities it is made of
+fn define(have: &str) -> Result<(), String> {
+    let have = parse(have)?;
+    println!("\t= {}", Display(have));
+    println!("\t{}", have.dimension().breakdown());
+    Ok(())
+}
+
+/// Prints `have` as a multiple of `want`, and the reciprocal
+fn convert(have: &str, want: &str) -> Result<(), String> {
+    let have = parse(have)?;
+    let want = parse(want)?;
+    let ratio = have
+        .value_in(&want)
+        .map_err(|err| format!("{}\n\t{}\n\t{}", err, Display(have), Display(want)))?;
+    println!("\t* {}", format_g(ratio));
+    println!("\t/ {}", format_g(1.0 / ratio));
+    Ok(())
+}
+
+fn interactive() -> Result<(), String> {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut prompt = |text: &str| -> Option<String> {
+        print!("{}", text);
+        io::stdout().flush().ok()?;
+        lines.next()?.ok()
+    };
+    loop {
+        let Some(have) = prompt("You have: ") else {
+            println!();
+            return Ok(());
+        };
+        let have = have.trim();
+        if have.is_empty() {
+            continue;
+        }
+        if let Err(err) = parse(have) {
+            println!("{}", err);
+            continue;
+        }
+        let Some(want) = prompt("You want: ") else {
+            println!();
+            return Ok(());
+        };
+        let want = want.trim();
+        let result = if want.is_empty() {
+            define(have)
+        } else {
+            convert(have, want)
+        };
+        if let Err(err) = result {
+            println!("{}", err);
+        }
+    }
+}
+
+/// A calculator prompt with variables, see `REPL_HELP`
+fn repl(mut session: Session) -> Result<(), String> {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush().map_err(|err| err.to_string())?;
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        let result = match line.trim().split_once(' ').unwrap_or((line.trim(), "")) {
+            ("", _) => continue,
+            // Assignments first, so `save = 3 m` is a variable rather than a file name
+            (_, rest) if rest.trim_start().starts_with('=') => session
+                .eval(&line)
+                .map(|evaluated| evaluated.to_string())
+                .map_err(|err| err.render(&line)),
+            ("quit" | "exit", "") => return Ok(()),
+            ("help", "") => Ok(REPL_HELP.to_string()),
+            ("vars", "") => Ok(session
+                .variables()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("save", file) => std::fs::File::create(file.trim())
+                .and_then(|file| session.save(file))
+                .map(|()| String::new())
+                .map_err(|err| format!("{}: {}", file.trim(), err)),
+            ("load", file) => load(session.clone(), file.trim()).map(|loaded| {
+                session = loaded;
+                String::new()
+            }),
+            _ => session
+                .eval(&line)
+                .map(|evaluated| evaluated.to_string())
+                .map_err(|err| err.render(&line)),
+        };
+        match result {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => println!("{}", output),
+            Err(err) => println!("{}", err),
+        }
+    }
+}
+
+fn load(mut session: Session, file: &str) -> Result<Session, String> {
+    let reader = std::fs::File::open(file).map(io::BufReader::new);
+    reader
+        .and_then(|reader| session.load(reader))
+        .map_err(|err| format!("{}: {}", file, err))?;
+    Ok(session)
+}
+
+/// Formats the SI value and unit of a quantity, with the same precision as the ratios
+struct Display(Quantity);
+
+impl std::fmt::Display for Display {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_g(self.0.value()))?;
+        if !self.0.dimension().is_dimensionless() {
+            write!(f, " {}", self.0.dimension())?;
+        }
+        Ok(())
+    }
+}
+
+/// Like C's `%.8g`: 8 significant digits, trailing zeros dropped, scientific notation for very
+/// large or small numbers
+fn format_g(value: f64) -> String {
+    const DIGITS: i32 = 8;
+    if value == 0.0 || !value.is_finite() {
+        return value.to_string();
+    }
+    let scientific = format!("{:.*e}", DIGITS as usize - 1, value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    if !(-4..DIGITS).contains(&exponent) {
+        format!("{}e{:+03}", trim_zeros(mantissa), exponent)
+    } else {
+        trim_zeros(&format!("{:.*}", (DIGITS - 1 - exponent) as usize, value)).to_string()
+    }
+}
+
+fn trim_zeros(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
@@ -0,0 +1,202 @@
+//! Numerical integration and differentiation of sampled values.
+//!
+//! The units follow from `Value` multiplication and division: integrating a speed over time
+//! steps gives a length, differentiating a length by time steps gives a speed.
+
+use std::ops::{Add, Div, Mul, Sub};
+
+use typenum::Integer;
+
+use crate::{Unit, UnitValue, Value};
+
+/// `value * factor` for an exact, unitless factor such as the weights of a quadrature rule
+fn scaled<V: UnitValue, P: Integer, U: Unit, K>(value: &V, factor: f64) -> Value<V, P, U, K> {
+    Value::new(value.apply_factor(factor))
+}
+
+/// `sum(weights[i] * samples[i])`, in the unit of the samples
+fn weighted_sum<V, P, U, K>(
+    samples: &[Value<V, P, U, K>],
+    weight: impl Fn(usize) -> f64,
+) -> Value<V, P, U, K>
+where
+    V: UnitValue + Add<Output = V>,
+    P: Integer,
+    U: Unit,
+{
+    let sum = samples
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| sample.0.apply_factor(weight(i)))
+        .reduce(Add::add)
+        .expect("no samples");
+    Value::new(sum)
+}
+
+/// The integral of samples taken every `step`, by the trapezoidal rule. Panics if there are
+/// fewer than two samples.
+pub fn trapezoid<V, P, U, K, X>(
+    samples: &[Value<V, P, U, K>],
+    step: X,
+) -> <Value<V, P, U, K> as Mul<X>>::Output
+where
+    V: UnitValue + Add<Output = V>,
+    P: Integer,
+    U: Unit,
+    Value<V, P, U, K>: Mul<X>,
+{
+    assert!(samples.len() >= 2, "the trapezoidal rule needs two samples");
+    let last = samples.len() - 1;
+    weighted_sum(samples, |i| if i == 0 || i == last { 0.5 } else { 1.0 }) * step
+}
+
+/// The integral of samples taken every `step`, by the composite Simpson's rule. Panics unless
+/// there is an odd number of at least three samples.
+pub fn simpson<V, P, U, K, X>(
+    samples: &[Value<V, P, U, K>],
+    step: X,
+) -> <Value<V, P, U, K> as Mul<X>>::Output
+where
+    V: UnitValue + Add<Output = V>,
+    P: Integer,
+    U: Unit,
+    Value<V, P, U, K>: Mul<X>,
+{
+    assert!(
+        samples.len() >= 3 && samples.len() % 2 == 1,
+        "Simpson's rule needs an odd number of samples"
+    );
+    let last = samples.len() - 1;
+    let weight = |i| match i {
+        _ if i == 0 || i == last => 1.0 / 3.0,
+        _ if i % 2 == 1 => 4.0 / 3.0,
+        _ => 2.0 / 3.0,
+    };
+    weighted_sum(samples, weight) * step
+}
+
+/// The integral of `samples[i]` taken at `points[i]`, by the trapezoidal rule. Panics if the
+/// lengths differ or there are fewer than two samples.
+pub fn trapezoid_at<V, P, U, K, X>(
+    points: &[X],
+    samples: &[Value<V, P, U, K>],
+) -> <Value<V, P, U, K> as Mul<X>>::Output
+where
+    V: UnitValue + Add<Output = V>,
+    P: Integer,
+    U: Unit,
+    X: Clone + Sub<Output = X>,
+    Value<V, P, U, K>: Mul<X>,
+    <Value<V, P, U, K> as Mul<X>>::Output: Add<Output = <Value<V, P, U, K> as Mul<X>>::Output>,
+{
+    assert_eq!(points.len(), samples.len(), "one sample per point");
+    assert!(samples.len() >= 2, "the trapezoidal rule needs two samples");
+    points
+        .windows(2)
+        .zip(samples.windows(2))
+        .map(|(x, y)| {
+            let mean = scaled::<V, P, U, K>(&(y[0].0.clone() + y[1].0.clone()), 0.5);
+            mean * (x[1].clone() - x[0].clone())
+        })
+        .reduce(Add::add)
+        .unwrap()
+}
+
+/// The derivative at every sample, for samples taken every `step`: central differences inside,
+/// one-sided differences at the ends. Panics if there are fewer than two samples.
+pub fn derivative<V, P, U, K, X>(
+    samples: &[Value<V, P, U, K>],
+    step: X,
+) -> Vec<<Value<V, P, U, K> as Div<X>>::Output>
+where
+    V: UnitValue + Sub<Output = V>,
+    P: Integer,
+    U: Unit,
+    X: Clone,
+    Value<V, P, U, K>: Div<X>,
+{
+    assert!(samples.len() >= 2, "a derivative needs two samples");
+    let last = samples.len() - 1;
+    (0..=last)
+        .map(|i| {
+            let (before, after) = (i.saturating_sub(1), (i + 1).min(last));
+            let difference = samples[after].0.clone() - samples[before].0.clone();
+            scaled::<V, P, U, K>(&difference, 1.0 / (after - before) as f64) / step.clone()
+        })
+        .collect()
+}
+
+/// The derivative of `f` at `x` by the central difference `(f(x + h) - f(x - h)) / 2h`
+pub fn central_difference<V, P, U, K, X>(
+    f: impl Fn(X) -> Value<V, P, U, K>,
+    x: X,
+    h: X,
+) -> <Value<V, P, U, K> as Div<X>>::Output
+where
+    V: UnitValue + Sub<Output = V>,
+    P: Integer,
+    U: Unit,
+    X: Clone + Add<Output = X> + Sub<Output = X>,
+    Value<V, P, U, K>: Div<X>,
+{
+    let difference = f(x.clone() + h.clone()).0 - f(x - h.clone()).0;
+    scaled::<V, P, U, K>(&difference, 0.5) / h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Coherent, Meter, Second},
+        close,
+        dimension::{Acceleration, Length, Speed},
+        prefix::Milli,
+    };
+    use typenum::{P3, Z0};
+
+    type Seconds = Value<f64, Z0, Second>;
+    type Speeds = Value<f64, Z0, Coherent<Speed>>;
+
+    #[test]
+    fn integration() {
+        // v = 3 t^2 m/s, so 64 m after 4 s
+        let speeds: Vec<Speeds> = (0..=8)
+            .map(|i| Value::new(3.0 * (i as f64 / 2.0).powi(2)))
+            .collect();
+        let step = Seconds::new(0.5);
+        let distance: Value<f64, Z0, Coherent<Length>> = simpson(&speeds, step);
+        assert!(close(*distance.value(), 64.0, 1e-9));
+        let rough = trapezoid(&speeds, step);
+        assert!(*rough.value() > 64.0 && *rough.value() < 65.0);
+
+        let times: Vec<Value<f64, P3, Second>> = [0.0, 500.0, 1500.0, 4000.0]
+            .iter()
+            .map(|&t| Milli::<Second>::new(t))
+            .collect();
+        let samples: Vec<Speeds> = times
+            .iter()
+            .map(|t| Value::new(2.0 * t.to_base().value()))
+            .collect();
+        let distance = trapezoid_at(&times, &samples);
+        assert!(close(*distance.to_base().value(), 16.0, 1e-9));
+    }
+
+    #[test]
+    fn differentiation() {
+        let positions: Vec<Value<f64, Z0, Meter>> =
+            (0..5).map(|i| Value::new((i * i) as f64)).collect();
+        let speeds = derivative(&positions, Seconds::new(1.0));
+        let speeds: Vec<f64> = speeds.iter().map(|v: &Speeds| *v.value()).collect();
+        assert_eq!(speeds, [1.0, 2.0, 4.0, 6.0, 7.0]);
+
+        let fall = |t: Seconds| Value::<f64, Z0, Meter>::new(-4.9 * t.value() * t.value());
+        let velocity: Speeds = central_difference(fall, Seconds::new(2.0), Seconds::new(1e-3));
+        assert!(close(*velocity.value(), -19.6, 1e-9));
+        let gravity: Value<f64, Z0, Coherent<Acceleration>> = central_difference(
+            |t| central_difference(fall, t, Seconds::new(1e-3)),
+            Seconds::new(2.0),
+            Seconds::new(1e-3),
+        );
+        assert!((gravity.value() + 9.8).abs() < 1e-6);
+    }
+}
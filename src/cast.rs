@@ -0,0 +1,103 @@
+//! Zero-copy views of plain numbers as values.
+//!
+//! `Value` is `#[repr(transparent)]` over its number (the unit and prefix only exist in the
+//! type), so a `&[f64]`, e.g. from a memory-mapped telemetry file, can be reinterpreted as a
+//! `&[Value<f64, P, U>]` and back without copying. With the `bytemuck` feature `Value` also
+//! implements `Pod`, `Zeroable` and `TransparentWrapper`, so it can be cast from raw bytes.
+
+use typenum::Integer;
+
+use crate::{Unit, Value};
+
+impl<V, P: Integer, U: Unit, K> Value<V, P, U, K> {
+    /// Views a number as a value in the unit `U`
+    pub fn from_ref(value: &V) -> &Self {
+        // SAFETY: `Value` is `repr(transparent)` over `V`
+        unsafe { &*(value as *const V as *const Self) }
+    }
+
+    pub fn from_mut(value: &mut V) -> &mut Self {
+        // SAFETY: `Value` is `repr(transparent)` over `V`
+        unsafe { &mut *(value as *mut V as *mut Self) }
+    }
+
+    /// Views numbers as values in the unit `U`, without copying
+    pub fn from_slice(values: &[V]) -> &[Self] {
+        // SAFETY: `Value` is `repr(transparent)` over `V`, so the layouts of the slices match
+        unsafe { std::slice::from_raw_parts(values.as_ptr() as *const Self, values.len()) }
+    }
+
+    pub fn from_slice_mut(values: &mut [V]) -> &mut [Self] {
+        // SAFETY: `Value` is `repr(transparent)` over `V`, so the layouts of the slices match
+        unsafe { std::slice::from_raw_parts_mut(values.as_mut_ptr() as *mut Self, values.len()) }
+    }
+
+    /// The numbers of `values`, in the unit `U`, without copying
+    pub fn as_values(values: &[Self]) -> &[V] {
+        // SAFETY: `Value` is `repr(transparent)` over `V`, so the layouts of the slices match
+        unsafe { std::slice::from_raw_parts(values.as_ptr() as *const V, values.len()) }
+    }
+
+    pub fn as_values_mut(values: &mut [Self]) -> &mut [V] {
+        // SAFETY: `Value` is `repr(transparent)` over `V`, so the layouts of the slices match
+        unsafe { std::slice::from_raw_parts_mut(values.as_mut_ptr() as *mut V, values.len()) }
+    }
+}
+
+// SAFETY: `Value` is `repr(transparent)` over `V`, the other fields have no size
+#[cfg(feature = "bytemuck")]
+unsafe impl<V: bytemuck::Zeroable, P: Integer, U: Unit, K> bytemuck::Zeroable
+    for Value<V, P, U, K>
+{
+}
+
+// SAFETY: as for `Zeroable`, and `Value` has no padding or invalid bit patterns beyond those of `V`
+#[cfg(feature = "bytemuck")]
+unsafe impl<V, P, U, K> bytemuck::Pod for Value<V, P, U, K>
+where
+    V: bytemuck::Pod,
+    P: Integer + Copy + 'static,
+    U: Unit + Copy + 'static,
+    K: Copy + 'static,
+{
+}
+
+// SAFETY: `Value` is `repr(transparent)` over `V`
+#[cfg(feature = "bytemuck")]
+unsafe impl<V, P: Integer, U: Unit, K> bytemuck::TransparentWrapper<V> for Value<V, P, U, K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base::Meter, prefix::Milli};
+    use typenum::{P3, Z0};
+
+    #[test]
+    fn slices() {
+        assert_eq!(std::mem::size_of::<Value<f64, P3, Meter>>(), 8);
+        let mut raw = [1.0, 2.5, -4.0];
+        let values = Value::<f64, P3, Meter>::from_slice(&raw);
+        assert_eq!(values[1], Milli::<Meter>::new(2.5));
+        assert_eq!(Value::as_values(values), [1.0, 2.5, -4.0]);
+
+        for value in Value::<f64, P3, Meter>::from_slice_mut(&mut raw) {
+            *value = value.convert::<Z0, Meter>().convert();
+        }
+        *Value::<f64, Z0, Meter>::from_mut(&mut raw[0]) = Value::new(0.5);
+        assert_eq!(raw, [0.5, 2.5, -4.0]);
+        assert_eq!(*Value::<f64, Z0, Meter>::from_ref(&raw[2]).value(), -4.0);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytes() {
+        // e.g. a memory-mapped record, aligned like its contents
+        let record = [1.5f64, 3.0];
+        let bytes: &[u8] = bytemuck::cast_slice(&record);
+        let values: &[Value<f64, P3, Meter>] = bytemuck::cast_slice(bytes);
+        assert_eq!(values[1], Milli::<Meter>::new(3.0));
+        assert_eq!(bytemuck::cast_slice::<_, u8>(values), bytes);
+        let zero: Value<f64, Z0, Meter> = bytemuck::Zeroable::zeroed();
+        assert_eq!(*zero.value(), 0.0);
+    }
+}
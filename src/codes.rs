@@ -0,0 +1,351 @@
+//! UN/CEFACT Recommendation 20 common codes and QUDT unit IRIs, for labelling values in data
+//! exchange (EDI, OPC UA).
+//!
+//! Every unit in `defs::UNITS` has an entry, as do the usual prefixed and compound units. Units
+//! of the same size are told apart by the `Kind` of a `Value`: an untagged `1/s` is `HTZ`, an
+//! `Activity` is `BQL`.
+
+use std::{any::TypeId, fmt::Display, sync::OnceLock};
+
+use typenum::{Integer, ToInt};
+
+use crate::{
+    dimension::Dimension,
+    kind::{Activity, AnyKind, EquivalentDose, Torque},
+    quantity::{DimensionError, Quantity},
+    registry::UnitRegistry,
+    Unit, Value,
+};
+
+/// The prefix of every QUDT unit IRI
+pub const QUDT_UNIT: &str = "http://qudt.org/vocab/unit/";
+
+/// A unit code, as the unit expression the builtin `UnitRegistry` understands
+struct Code {
+    unit: &'static str,
+    rec20: &'static str,
+    /// The IRI after `QUDT_UNIT`
+    qudt: &'static str,
+    /// Only used for values of this kind
+    kind: Option<fn() -> TypeId>,
+}
+
+macro_rules! codes {
+    ($($unit:literal $rec20:literal $qudt:literal $(: $kind:ty)?;)+) => {
+        static CODES: &[Code] = &[
+            $(Code {
+                unit: $unit,
+                rec20: $rec20,
+                qudt: $qudt,
+                kind: codes!(@kind $($kind)?),
+            },)+
+        ];
+    };
+    (@kind) => { None };
+    (@kind $kind:ty) => { Some(TypeId::of::<$kind>) };
+}
+
+codes! {
+    // Length
+    "m" "MTR" "M";
+    "km" "KMT" "KiloM";
+    "cm" "CMT" "CentiM";
+    "mm" "MMT" "MilliM";
+    "um" "4H" "MicroM";
+    "nm" "C45" "NanoM";
+    "in" "INH" "IN";
+    "ft" "FOT" "FT";
+    "yd" "YRD" "YD";
+    "mi" "SMI" "MI";
+    "nmi" "NMI" "MI_N";
+    // Time
+    "s" "SEC" "SEC";
+    "ms" "C26" "MilliSEC";
+    "us" "B98" "MicroSEC";
+    "ns" "C47" "NanoSEC";
+    "min" "MIN" "MIN";
+    "h" "HUR" "HR";
+    "d" "DAY" "DAY";
+    // Mass
+    "kg" "KGM" "KiloGM";
+    "g" "GRM" "GM";
+    "mg" "MGM" "MilliGM";
+    "ug" "MC" "MicroGM";
+    "t" "TNE" "TONNE";
+    "oz" "ONZ" "OZ";
+    "lb" "LBR" "LB";
+    // Other base units
+    "A" "AMP" "A";
+    "mA" "4K" "MilliA";
+    "kA" "B22" "KiloA";
+    "K" "KEL" "K";
+    "mol" "C34" "MOL";
+    "kmol" "B45" "KiloMOL";
+    "mmol" "C18" "MilliMOL";
+    "cd" "CDL" "CD";
+    // Derived units
+    "rad" "C81" "RAD";
+    "sr" "D27" "SR";
+    "Hz" "HTZ" "HZ";
+    "kHz" "KHZ" "KiloHZ";
+    "MHz" "MHZ" "MegaHZ";
+    "GHz" "A86" "GigaHZ";
+    "N" "NEU" "N";
+    "kN" "B47" "KiloN";
+    "Pa" "PAL" "PA";
+    "hPa" "A97" "HectoPA";
+    "kPa" "KPA" "KiloPA";
+    "MPa" "MPA" "MegaPA";
+    "bar" "BAR" "BAR";
+    "mbar" "MBR" "MilliBAR";
+    "atm" "ATM" "ATM";
+    "psi" "PS" "PSI";
+    "J" "JOU" "J";
+    "kJ" "KJO" "KiloJ";
+    "MJ" "3B" "MegaJ";
+    "Wh" "WHR" "W-HR";
+    "kWh" "KWH" "KiloW-HR";
+    "eV" "A53" "EV";
+    "cal" "D35" "CAL_TH";
+    "W" "WTT" "W";
+    "mW" "C31" "MilliW";
+    "kW" "KWT" "KiloW";
+    "MW" "MAW" "MegaW";
+    "C" "COU" "C";
+    "V" "VLT" "V";
+    "mV" "2Z" "MilliV";
+    "kV" "KVT" "KiloV";
+    "ohm" "OHM" "OHM";
+    "kohm" "B49" "KiloOHM";
+    "Mohm" "B75" "MegaOHM";
+    "S" "SIE" "S";
+    "F" "FAR" "FARAD";
+    "uF" "4O" "MicroFARAD";
+    "nF" "C41" "NanoFARAD";
+    "pF" "4T" "PicoFARAD";
+    "Wb" "WEB" "WB";
+    "T" "D33" "T";
+    "H" "81" "H";
+    "lm" "LUM" "LM";
+    "lx" "LUX" "LUX";
+    "Gy" "A95" "GRAY";
+    "kat" "KAT" "KAT";
+    "Bq" "BQL" "BQ": Activity;
+    "Sv" "D13" "SV": EquivalentDose;
+    "N*m" "NU" "N-M": Torque;
+    // Compound units
+    "m^2" "MTK" "M2";
+    "m^3" "MTQ" "M3";
+    "L" "LTR" "L";
+    "mL" "MLT" "MilliL";
+    "gal" "GLL" "GAL_US";
+    "m/s" "MTS" "M-PER-SEC";
+    "km/h" "KMH" "KiloM-PER-HR";
+    "mph" "HM" "MI-PER-HR";
+    "kn" "KNT" "KN";
+    "m/s^2" "MSK" "M-PER-SEC2";
+    "kg/m^3" "KMQ" "KiloGM-PER-M3";
+}
+
+/// Why a code couldn't be used
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodeError {
+    /// Not a code in the table
+    Unknown(String),
+    /// The unit of the code doesn't fit the value
+    Dimension(Box<DimensionError>),
+}
+
+impl Display for CodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodeError::Unknown(code) => write!(f, "unknown unit code '{}'", code),
+            CodeError::Dimension(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+fn size(code: &Code) -> Quantity {
+    static SIZES: OnceLock<Vec<Quantity>> = OnceLock::new();
+    let sizes = SIZES.get_or_init(|| {
+        // The table only uses units of the builtin registry, see the `table` test
+        let registry = UnitRegistry::builtin();
+        CODES
+            .iter()
+            .map(|code| registry.parse(code.unit).unwrap())
+            .collect()
+    });
+    sizes[CODES
+        .iter()
+        .position(|entry| std::ptr::eq(entry, code))
+        .unwrap()]
+}
+
+/// The entry for a unit of size `unit` and kind `kind`, preferring one made for that kind
+fn find(unit: &Quantity, kind: TypeId) -> Option<&'static Code> {
+    let same = |code: &&Code| {
+        let size = size(code);
+        size.dimension() == unit.dimension()
+            && (size.value() - unit.value()).abs() <= 1e-12 * unit.value().abs()
+    };
+    let tagged = CODES
+        .iter()
+        .filter(same)
+        .find(|code| code.kind.is_some_and(|kind_of| kind_of() == kind));
+    tagged.or_else(|| CODES.iter().filter(same).find(|code| code.kind.is_none()))
+}
+
+fn by_rec20(code: &str) -> Result<&'static Code, CodeError> {
+    CODES
+        .iter()
+        .find(|entry| entry.rec20 == code)
+        .ok_or_else(|| CodeError::Unknown(code.to_string()))
+}
+
+/// Accepts the full IRI, a `unit:` prefixed name or the bare name
+fn by_qudt(iri: &str) -> Result<&'static Code, CodeError> {
+    let name = iri
+        .strip_prefix(QUDT_UNIT)
+        .or_else(|| iri.strip_prefix("unit:"))
+        .unwrap_or(iri);
+    CODES
+        .iter()
+        .find(|entry| entry.qudt == name)
+        .ok_or_else(|| CodeError::Unknown(iri.to_string()))
+}
+
+fn convert(quantity: &Quantity, code: &Code) -> Result<f64, CodeError> {
+    quantity
+        .value_in(&size(code))
+        .map_err(|err| CodeError::Dimension(Box::new(err)))
+}
+
+/// The Rec 20 code of a unit of size `unit`, e.g. `KMH` for `"km/h".parse()`
+pub fn rec20_code(unit: &Quantity) -> Option<&'static str> {
+    find(unit, TypeId::of::<AnyKind>()).map(|code| code.rec20)
+}
+
+/// The QUDT IRI of a unit of size `unit`
+pub fn qudt_iri(unit: &Quantity) -> Option<String> {
+    find(unit, TypeId::of::<AnyKind>()).map(|code| format!("{}{}", QUDT_UNIT, code.qudt))
+}
+
+impl Quantity {
+    /// `value` of the unit with Rec 20 code `code`
+    pub fn from_rec20(value: f64, code: &str) -> Result<Self, CodeError> {
+        Ok(size(by_rec20(code)?) * value)
+    }
+
+    /// How many of the unit with Rec 20 code `code` this is
+    pub fn to_rec20(&self, code: &str) -> Result<f64, CodeError> {
+        convert(self, by_rec20(code)?)
+    }
+
+    /// `value` of the QUDT unit `iri`
+    pub fn from_qudt(value: f64, iri: &str) -> Result<Self, CodeError> {
+        Ok(size(by_qudt(iri)?) * value)
+    }
+
+    /// How many of the QUDT unit `iri` this is
+    pub fn to_qudt(&self, iri: &str) -> Result<f64, CodeError> {
+        convert(self, by_qudt(iri)?)
+    }
+}
+
+impl<P: Integer + ToInt<i32>, U: Unit, K: 'static> Value<f64, P, U, K>
+where
+    U::Dimension: Dimension,
+{
+    fn code(&self) -> Option<&'static Code> {
+        let unit = Quantity::from(Value::<f64, P, U, K>::new(1.0));
+        find(&unit, TypeId::of::<K>())
+    }
+
+    /// The Rec 20 code of this value's unit, e.g. `KMT` for `Kilo<Meter>`
+    pub fn rec20_code(&self) -> Option<&'static str> {
+        self.code().map(|code| code.rec20)
+    }
+
+    /// The QUDT IRI of this value's unit
+    pub fn qudt_iri(&self) -> Option<String> {
+        self.code()
+            .map(|code| format!("{}{}", QUDT_UNIT, code.qudt))
+    }
+
+    /// `value` of the unit with Rec 20 code `code`, which must have the dimension of `U`
+    pub fn from_rec20(value: f64, code: &str) -> Result<Self, CodeError> {
+        Quantity::from_rec20(value, code)?
+            .to_value()
+            .map_err(|err| CodeError::Dimension(Box::new(err)))
+    }
+
+    /// `value` of the QUDT unit `iri`, which must have the dimension of `U`
+    pub fn from_qudt(value: f64, iri: &str) -> Result<Self, CodeError> {
+        Quantity::from_qudt(value, iri)?
+            .to_value()
+            .map_err(|err| CodeError::Dimension(Box::new(err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Gram, Meter, Second, Unitless},
+        defs::UNITS,
+        prefix::Kilo,
+    };
+    use typenum::Z0;
+
+    #[test]
+    fn table() {
+        let registry = UnitRegistry::builtin();
+        for code in CODES {
+            assert!(registry.parse(code.unit).is_ok(), "{}", code.unit);
+            assert_eq!(by_rec20(code.rec20).unwrap().unit, code.unit);
+            assert_eq!(by_qudt(code.qudt).unwrap().unit, code.unit);
+        }
+        for unit in UNITS {
+            let kind = CODES
+                .iter()
+                .find(|code| code.unit == unit.symbol)
+                .and_then(|code| code.kind)
+                .map_or(TypeId::of::<AnyKind>(), |kind| kind());
+            assert!(find(&unit.quantity(), kind).is_some(), "{}", unit.symbol);
+        }
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(Kilo::<Meter>::new(1f64).rec20_code(), Some("KMT"));
+        assert_eq!(Kilo::<Gram>::new(1f64).rec20_code(), Some("KGM"));
+        assert_eq!(
+            (Meter::new(1f64) / Second::new(1f64)).qudt_iri().unwrap(),
+            "http://qudt.org/vocab/unit/M-PER-SEC"
+        );
+        let per_second = Unitless::new(1f64) / Second::new(1f64);
+        assert_eq!(per_second.rec20_code(), Some("HTZ"));
+        assert_eq!(
+            per_second.reinterpret_kind::<Activity>().rec20_code(),
+            Some("BQL")
+        );
+
+        let speed = Value::<f64, Z0, Meter>::from_rec20(3.0, "KMT").unwrap();
+        assert_eq!(speed, Meter::new(3000));
+        assert!(Value::<f64, Z0, Meter>::from_qudt(1.0, "unit:SEC").is_err());
+        assert_eq!(
+            Quantity::from_qudt(36.0, "http://qudt.org/vocab/unit/KiloM-PER-HR")
+                .unwrap()
+                .to_rec20("MTS")
+                .unwrap(),
+            10.0
+        );
+        assert_eq!(rec20_code(&"km/h".parse().unwrap()), Some("KMH"));
+        assert!(matches!(
+            Quantity::from_rec20(1.0, "XYZ"),
+            Err(CodeError::Unknown(_))
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+//! Complex numbers, for phasors and impedances in AC circuit analysis.
+//!
+//! A `Value<Complex, _, _>` behaves like any other value: dividing a voltage phasor by a current
+//! phasor gives an impedance in ohms. The magnitude of a phasor keeps its unit, the phase is a
+//! plain angle in radians.
+
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use typenum::{Integer, ToInt};
+
+use crate::{Unit, UnitValue, Value};
+
+/// `re + im i`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const I: Self = Self { re: 0.0, im: 1.0 };
+
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// `magnitude * e^(i phase)`, with `phase` in radians
+    pub fn from_polar(magnitude: f64, phase: f64) -> Self {
+        Self::new(magnitude * phase.cos(), magnitude * phase.sin())
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// The argument in radians, in `(-π, π]`
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl UnitValue for Complex {
+    fn apply_prefix(&self, power_of_ten: i32) -> Self {
+        self.apply_factor(10f64.powi(power_of_ten))
+    }
+
+    fn apply_factor(&self, factor: f64) -> Self {
+        Self::new(self.re * factor, self.im * factor)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let denominator = rhs.re * rhs.re + rhs.im * rhs.im;
+        let numerator = self * rhs.conj();
+        Self::new(numerator.re / denominator, numerator.im / denominator)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        self.apply_factor(rhs)
+    }
+}
+
+impl Mul<Complex> for f64 {
+    type Output = Complex;
+    fn mul(self, rhs: Complex) -> Complex {
+        rhs.apply_factor(self)
+    }
+}
+
+impl Div<f64> for Complex {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.re / rhs, self.im / rhs)
+    }
+}
+
+impl Div<Complex> for f64 {
+    type Output = Complex;
+    fn div(self, rhs: Complex) -> Complex {
+        Complex::from(self) / rhs
+    }
+}
+
+/// Formats as `3+4i`, with the precision applying to both parts
+impl Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.re, f)?;
+        if self.im.is_sign_negative() {
+            write!(f, "-")?;
+        } else {
+            write!(f, "+")?;
+        }
+        Display::fmt(&self.im.abs(), f)?;
+        write!(f, "i")
+    }
+}
+
+impl<P: Integer + ToInt<i32>, U: Unit, K> Value<Complex, P, U, K> {
+    /// The phasor with the given magnitude and phase (in radians)
+    pub fn from_polar(magnitude: Value<f64, P, U, K>, phase: f64) -> Self {
+        Value::new(Complex::from_polar(*magnitude.value(), phase))
+    }
+
+    /// The magnitude, in the units of `self`
+    pub fn magnitude(&self) -> Value<f64, P, U, K> {
+        Value::new(self.value().norm())
+    }
+
+    /// The phase in radians
+    pub fn phase(&self) -> f64 {
+        self.value().arg()
+    }
+
+    pub fn re(&self) -> Value<f64, P, U, K> {
+        Value::new(self.value().re)
+    }
+
+    pub fn im(&self) -> Value<f64, P, U, K> {
+        Value::new(self.value().im)
+    }
+
+    pub fn conj(&self) -> Self {
+        Value::new(self.value().conj())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Ampere, Coherent},
+        close,
+        dimension::{Power, Resistance, Voltage},
+        prefix::Milli,
+    };
+    use std::f64::consts::FRAC_PI_2;
+    use typenum::Z0;
+
+    #[test]
+    fn phasors() {
+        let voltage = Value::<_, Z0, Coherent<Voltage>>::new(Complex::new(230.0, 0.0));
+        let current = Value::from_polar(Milli::<Ampere>::new(500f64), -FRAC_PI_2 / 3.0);
+        let impedance: Value<Complex, _, Coherent<Resistance>> = voltage / current;
+        assert!(close(
+            *impedance.magnitude().to_base().value(),
+            460.0,
+            1e-12
+        ));
+        assert!(close(impedance.phase(), FRAC_PI_2 / 3.0, 1e-12));
+        assert!(close(
+            *impedance.re().to_base().value(),
+            460.0 * 3f64.sqrt() / 2.0,
+            1e-12
+        ));
+
+        // S = V I*, the real part is the active power
+        let power: Value<Complex, _, Coherent<Power>> = voltage * current.conj();
+        assert!(close(
+            *power.re().to_base().value(),
+            115.0 * 3f64.sqrt() / 2.0,
+            1e-12
+        ));
+        assert!(close(*power.im().to_base().value(), 57.5, 1e-12));
+        assert!(close(
+            *(impedance * current).magnitude().to_base().value(),
+            230.0,
+            1e-12
+        ));
+        assert_eq!(format!("{:.1}", Complex::new(3.0, -4.0)), "3.0-4.0i");
+        assert_eq!(Complex::I * Complex::I, Complex::from(-1.0));
+    }
+}
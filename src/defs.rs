@@ -0,0 +1,114 @@
+use crate::quantity::{DynDimension, Quantity};
+
+/// A unit known to `registry::UnitRegistry::builtin`, as a multiple of the coherent SI unit of
+/// its dimension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitDef {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub plural: &'static str,
+    /// Other spellings, e.g. `metre`
+    pub aliases: &'static [&'static str],
+    pub factor: f64,
+    pub dimension: DynDimension,
+}
+
+impl UnitDef {
+    pub fn quantity(&self) -> Quantity {
+        Quantity::new(self.factor, self.dimension)
+    }
+}
+
+const fn dim(exponents: [i32; 7]) -> DynDimension {
+    DynDimension::from_ints(exponents)
+}
+
+// Length, Time, Mass, Amount, Current, Tempature, LuminousIntesity
+const NONE: DynDimension = DynDimension::NONE;
+const LENGTH: DynDimension = dim([1, 0, 0, 0, 0, 0, 0]);
+const TIME: DynDimension = dim([0, 1, 0, 0, 0, 0, 0]);
+const MASS: DynDimension = dim([0, 0, 1, 0, 0, 0, 0]);
+const VOLUME: DynDimension = dim([3, 0, 0, 0, 0, 0, 0]);
+const SPEED: DynDimension = dim([1, -1, 0, 0, 0, 0, 0]);
+const ENERGY: DynDimension = dim([2, -2, 1, 0, 0, 0, 0]);
+const PRESSURE: DynDimension = dim([-1, -2, 1, 0, 0, 0, 0]);
+const FREQUENCY: DynDimension = dim([0, -1, 0, 0, 0, 0, 0]);
+
+macro_rules! units {
+    (@plural $name:literal) => { concat!($name, "s") };
+    (@plural $name:literal $plural:literal) => { $plural };
+    ($(
+        $symbol:literal $name:literal $(/ $plural:literal)? $([$($alias:literal),+])?
+            = $factor:expr, $dimension:expr;
+    )+) => {
+        /// The units `Quantity::from_str` understands. Any of them can be prefixed with a
+        /// symbol or name from `prefix::PREFIXES`, e.g. `km` or `kilometer`. The plural is the
+        /// name with an `s` unless it is given after a `/`.
+        pub const UNITS: &[UnitDef] = &[
+            $(UnitDef {
+                symbol: $symbol,
+                name: $name,
+                plural: units!(@plural $name $($plural)?),
+                aliases: &[$($($alias),+)?],
+                factor: $factor,
+                dimension: $dimension,
+            },)+
+        ];
+    };
+}
+
+units! {
+    // SI base units
+    "m" "meter" ["metre"] = 1.0, LENGTH;
+    "s" "second" = 1.0, TIME;
+    "g" "gram" = 1e-3, MASS;
+    "mol" "mole" = 1.0, dim([0, 0, 0, 1, 0, 0, 0]);
+    "A" "ampere" = 1.0, dim([0, 0, 0, 0, 1, 0, 0]);
+    "K" "kelvin" = 1.0, dim([0, 0, 0, 0, 0, 1, 0]);
+    "cd" "candela" = 1.0, dim([0, 0, 0, 0, 0, 0, 1]);
+    // SI derived units
+    "rad" "radian" = 1.0, NONE;
+    "sr" "steradian" = 1.0, NONE;
+    "Hz" "hertz" / "hertz" = 1.0, FREQUENCY;
+    "N" "newton" = 1.0, dim([1, -2, 1, 0, 0, 0, 0]);
+    "Pa" "pascal" = 1.0, PRESSURE;
+    "J" "joule" = 1.0, ENERGY;
+    "W" "watt" = 1.0, dim([2, -3, 1, 0, 0, 0, 0]);
+    "C" "coulomb" = 1.0, dim([0, 1, 0, 0, 1, 0, 0]);
+    "V" "volt" = 1.0, dim([2, -3, 1, 0, -1, 0, 0]);
+    "ohm" "ohm" ["Ω"] = 1.0, dim([2, -3, 1, 0, -2, 0, 0]);
+    "S" "siemens" / "siemens" = 1.0, dim([-2, 3, -1, 0, 2, 0, 0]);
+    "F" "farad" = 1.0, dim([-2, 4, -1, 0, 2, 0, 0]);
+    "Wb" "weber" = 1.0, dim([2, -2, 1, 0, -1, 0, 0]);
+    "T" "tesla" = 1.0, dim([0, -2, 1, 0, -1, 0, 0]);
+    "H" "henry" / "henries" = 1.0, dim([2, -2, 1, 0, -2, 0, 0]);
+    "lm" "lumen" = 1.0, dim([0, 0, 0, 0, 0, 0, 1]);
+    "lx" "lux" / "lux" = 1.0, dim([-2, 0, 0, 0, 0, 0, 1]);
+    "Bq" "becquerel" = 1.0, FREQUENCY;
+    "Gy" "gray" = 1.0, dim([2, -2, 0, 0, 0, 0, 0]);
+    "Sv" "sievert" = 1.0, dim([2, -2, 0, 0, 0, 0, 0]);
+    "kat" "katal" = 1.0, dim([0, -1, 0, 1, 0, 0, 0]);
+    // Accepted for use with the SI
+    "min" "minute" = 60.0, TIME;
+    "h" "hour" = 3600.0, TIME;
+    "d" "day" = 86400.0, TIME;
+    "L" "liter" ["l", "litre"] = 1e-3, VOLUME;
+    "t" "tonne" ["metricton"] = 1e3, MASS;
+    "bar" "bar" = 1e5, PRESSURE;
+    "eV" "electronvolt" = 1.602176634e-19, ENERGY;
+    "Wh" "watthour" = 3600.0, ENERGY;
+    // Customary units
+    "in" "inch" / "inches" = 0.0254, LENGTH;
+    "ft" "foot" / "feet" = 0.3048, LENGTH;
+    "yd" "yard" = 0.9144, LENGTH;
+    "mi" "mile" = 1609.344, LENGTH;
+    "nmi" "nauticalmile" = 1852.0, LENGTH;
+    "mph" "milesperhour" / "milesperhour" = 1609.344 / 3600.0, SPEED;
+    "kn" "knot" = 1852.0 / 3600.0, SPEED;
+    "oz" "ounce" = 0.028349523125, MASS;
+    "lb" "pound" = 0.45359237, MASS;
+    "gal" "gallon" = 3.785411784e-3, VOLUME;
+    "atm" "atmosphere" = 101325.0, PRESSURE;
+    "psi" "psi" / "psi" = 6894.757293168361, PRESSURE;
+    "cal" "calorie" = 4.184, ENERGY;
+}
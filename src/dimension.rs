@@ -0,0 +1,287 @@
+use std::{
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+use typenum::{Diff, Gcd, Gcf, Integer, Negate, Prod, Quot, Sum, N1, N2, N3, P1, P2, P3, P4, Z0};
+
+/// Type level exponents of the seven base quantities, in the same order as the `unit_types!`
+/// traits: Length, Time, Mass, Amount, Current, Tempature and LuminousIntesity. Each exponent
+/// is a `Frac`, so half powers such as the `1/√Hz` of a noise density can be written down.
+///
+/// Two units describe the same kind of quantity exactly when their `Dimension`s are the same
+/// type, which is what `Value::convert` and friends check.
+pub struct Dim<L, T, M, N, I, Th, J>(PhantomData<(L, T, M, N, I, Th, J)>);
+
+/// A `Dim` with only whole exponents
+pub type IntDim<L, T, M, N, I, Th, J> = Dim<
+    Frac<L, P1>,
+    Frac<T, P1>,
+    Frac<M, P1>,
+    Frac<N, P1>,
+    Frac<I, P1>,
+    Frac<Th, P1>,
+    Frac<J, P1>,
+>;
+
+pub type Dimensionless = IntDim<Z0, Z0, Z0, Z0, Z0, Z0, Z0>;
+
+// The base quantities
+pub type Length = IntDim<P1, Z0, Z0, Z0, Z0, Z0, Z0>;
+pub type Time = IntDim<Z0, P1, Z0, Z0, Z0, Z0, Z0>;
+pub type Mass = IntDim<Z0, Z0, P1, Z0, Z0, Z0, Z0>;
+pub type Amount = IntDim<Z0, Z0, Z0, P1, Z0, Z0, Z0>;
+pub type Current = IntDim<Z0, Z0, Z0, Z0, P1, Z0, Z0>;
+pub type Tempature = IntDim<Z0, Z0, Z0, Z0, Z0, P1, Z0>;
+pub type LuminousIntesity = IntDim<Z0, Z0, Z0, Z0, Z0, Z0, P1>;
+
+// Common derived quantities
+pub type Area = IntDim<P2, Z0, Z0, Z0, Z0, Z0, Z0>;
+pub type Volume = IntDim<P3, Z0, Z0, Z0, Z0, Z0, Z0>;
+pub type Frequency = IntDim<Z0, N1, Z0, Z0, Z0, Z0, Z0>;
+pub type Speed = IntDim<P1, N1, Z0, Z0, Z0, Z0, Z0>;
+pub type Acceleration = IntDim<P1, N2, Z0, Z0, Z0, Z0, Z0>;
+pub type Momentum = IntDim<P1, N1, P1, Z0, Z0, Z0, Z0>;
+pub type Force = IntDim<P1, N2, P1, Z0, Z0, Z0, Z0>;
+pub type Energy = IntDim<P2, N2, P1, Z0, Z0, Z0, Z0>;
+pub type Power = IntDim<P2, N3, P1, Z0, Z0, Z0, Z0>;
+pub type Pressure = IntDim<N1, N2, P1, Z0, Z0, Z0, Z0>;
+pub type Density = IntDim<N3, Z0, P1, Z0, Z0, Z0, Z0>;
+pub type Charge = IntDim<Z0, P1, Z0, Z0, P1, Z0, Z0>;
+pub type Voltage = IntDim<P2, N3, P1, Z0, N1, Z0, Z0>;
+pub type Resistance = IntDim<P2, N3, P1, Z0, N2, Z0, Z0>;
+pub type Capacitance = IntDim<N2, P4, N1, Z0, P2, Z0, Z0>;
+pub type Catalytic = IntDim<Z0, N1, Z0, P1, Z0, Z0, Z0>;
+pub type ElectricField = IntDim<P1, N3, P1, Z0, N1, Z0, Z0>;
+pub type MagneticFlux = IntDim<P2, N2, P1, Z0, N1, Z0, Z0>;
+pub type MagneticFluxDensity = IntDim<Z0, N2, P1, Z0, N1, Z0, Z0>;
+pub type MagneticFieldStrength = IntDim<N1, Z0, Z0, Z0, P1, Z0, Z0>;
+
+pub trait Dimension {
+    /// The exponents as `(numerator, denominator)` pairs
+    fn exponents() -> [(i32, i32); 7];
+}
+
+impl<L, T, M, N, I, Th, J> Dimension for Dim<L, T, M, N, I, Th, J>
+where
+    L: Exponent,
+    T: Exponent,
+    M: Exponent,
+    N: Exponent,
+    I: Exponent,
+    Th: Exponent,
+    J: Exponent,
+{
+    fn exponents() -> [(i32, i32); 7] {
+        [
+            L::ratio(),
+            T::ratio(),
+            M::ratio(),
+            N::ratio(),
+            I::ratio(),
+            Th::ratio(),
+            J::ratio(),
+        ]
+    }
+}
+
+/// The rational exponent `N / D`.
+///
+/// Every operation keeps the fraction in lowest terms with a positive denominator, so equal
+/// exponents are always the same type.
+pub struct Frac<N, D>(PhantomData<(N, D)>);
+
+pub trait Exponent {
+    fn ratio() -> (i32, i32);
+}
+
+impl<N: Integer, D: Integer> Exponent for Frac<N, D> {
+    fn ratio() -> (i32, i32) {
+        (N::to_i32(), D::to_i32())
+    }
+}
+
+/// Brings `N / D` into lowest terms
+pub trait Reduce {
+    type Output;
+    fn reduce(self) -> Self::Output;
+}
+
+impl<N, D> Reduce for Frac<N, D>
+where
+    N: Gcd<D> + Div<Gcf<N, D>>,
+    D: Div<Gcf<N, D>>,
+{
+    type Output = Frac<Quot<N, Gcf<N, D>>, Quot<D, Gcf<N, D>>>;
+    fn reduce(self) -> Self::Output {
+        Frac(PhantomData)
+    }
+}
+
+type Reduced<F> = <F as Reduce>::Output;
+
+// a/b + c/d => (ad + cb) / bd
+impl<N1, D1, N2, D2> Add<Frac<N2, D2>> for Frac<N1, D1>
+where
+    N1: Mul<D2>,
+    N2: Mul<D1>,
+    Prod<N1, D2>: Add<Prod<N2, D1>>,
+    D1: Mul<D2>,
+    Frac<Sum<Prod<N1, D2>, Prod<N2, D1>>, Prod<D1, D2>>: Reduce,
+{
+    type Output = Reduced<Frac<Sum<Prod<N1, D2>, Prod<N2, D1>>, Prod<D1, D2>>>;
+    fn add(self, _rhs: Frac<N2, D2>) -> Self::Output {
+        Frac(PhantomData).reduce()
+    }
+}
+
+// a/b - c/d => (ad - cb) / bd
+impl<N1, D1, N2, D2> Sub<Frac<N2, D2>> for Frac<N1, D1>
+where
+    N1: Mul<D2>,
+    N2: Mul<D1>,
+    Prod<N1, D2>: Sub<Prod<N2, D1>>,
+    D1: Mul<D2>,
+    Frac<Diff<Prod<N1, D2>, Prod<N2, D1>>, Prod<D1, D2>>: Reduce,
+{
+    type Output = Reduced<Frac<Diff<Prod<N1, D2>, Prod<N2, D1>>, Prod<D1, D2>>>;
+    fn sub(self, _rhs: Frac<N2, D2>) -> Self::Output {
+        Frac(PhantomData).reduce()
+    }
+}
+
+impl<N: Neg, D> Neg for Frac<N, D> {
+    type Output = Frac<Negate<N>, D>;
+    fn neg(self) -> Self::Output {
+        Frac(PhantomData)
+    }
+}
+
+// a/b * E => aE / b
+impl<N, D, E: Integer> Mul<E> for Frac<N, D>
+where
+    N: Mul<E>,
+    Frac<Prod<N, E>, D>: Reduce,
+{
+    type Output = Reduced<Frac<Prod<N, E>, D>>;
+    fn mul(self, _rhs: E) -> Self::Output {
+        Frac(PhantomData).reduce()
+    }
+}
+
+// a/b / E => a / bE, `E` must be positive to keep the denominator positive
+impl<N, D, E: Integer> Div<E> for Frac<N, D>
+where
+    D: Mul<E>,
+    Frac<N, Prod<D, E>>: Reduce,
+{
+    type Output = Reduced<Frac<N, Prod<D, E>>>;
+    fn div(self, _rhs: E) -> Self::Output {
+        Frac(PhantomData).reduce()
+    }
+}
+
+// Dim * Dim => exponents add
+impl<L, T, M, N, I, Th, J, L2, T2, M2, N2, I2, Th2, J2> Add<Dim<L2, T2, M2, N2, I2, Th2, J2>>
+    for Dim<L, T, M, N, I, Th, J>
+where
+    L: Add<L2>,
+    T: Add<T2>,
+    M: Add<M2>,
+    N: Add<N2>,
+    I: Add<I2>,
+    Th: Add<Th2>,
+    J: Add<J2>,
+{
+    type Output = Dim<L::Output, T::Output, M::Output, N::Output, I::Output, Th::Output, J::Output>;
+    fn add(self, _rhs: Dim<L2, T2, M2, N2, I2, Th2, J2>) -> Self::Output {
+        Dim(PhantomData)
+    }
+}
+
+// Dim / Dim => exponents subtract
+impl<L, T, M, N, I, Th, J, L2, T2, M2, N2, I2, Th2, J2> Sub<Dim<L2, T2, M2, N2, I2, Th2, J2>>
+    for Dim<L, T, M, N, I, Th, J>
+where
+    L: Sub<L2>,
+    T: Sub<T2>,
+    M: Sub<M2>,
+    N: Sub<N2>,
+    I: Sub<I2>,
+    Th: Sub<Th2>,
+    J: Sub<J2>,
+{
+    type Output = Dim<L::Output, T::Output, M::Output, N::Output, I::Output, Th::Output, J::Output>;
+    fn sub(self, _rhs: Dim<L2, T2, M2, N2, I2, Th2, J2>) -> Self::Output {
+        Dim(PhantomData)
+    }
+}
+
+// 1 / Dim => exponents negate
+impl<L, T, M, N, I, Th, J> Neg for Dim<L, T, M, N, I, Th, J>
+where
+    L: Neg,
+    T: Neg,
+    M: Neg,
+    N: Neg,
+    I: Neg,
+    Th: Neg,
+    J: Neg,
+{
+    type Output = Dim<L::Output, T::Output, M::Output, N::Output, I::Output, Th::Output, J::Output>;
+    fn neg(self) -> Self::Output {
+        Dim(PhantomData)
+    }
+}
+
+// Dim ^ E => exponents multiply
+impl<L, T, M, N, I, Th, J, E: Integer> Mul<E> for Dim<L, T, M, N, I, Th, J>
+where
+    L: Mul<E>,
+    T: Mul<E>,
+    M: Mul<E>,
+    N: Mul<E>,
+    I: Mul<E>,
+    Th: Mul<E>,
+    J: Mul<E>,
+{
+    type Output = Dim<L::Output, T::Output, M::Output, N::Output, I::Output, Th::Output, J::Output>;
+    fn mul(self, _rhs: E) -> Self::Output {
+        Dim(PhantomData)
+    }
+}
+
+// Dim ^ (1/E) => exponents divide
+impl<L, T, M, N, I, Th, J, E: Integer> Div<E> for Dim<L, T, M, N, I, Th, J>
+where
+    L: Div<E>,
+    T: Div<E>,
+    M: Div<E>,
+    N: Div<E>,
+    I: Div<E>,
+    Th: Div<E>,
+    J: Div<E>,
+{
+    type Output = Dim<L::Output, T::Output, M::Output, N::Output, I::Output, Th::Output, J::Output>;
+    fn div(self, _rhs: E) -> Self::Output {
+        Dim(PhantomData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{Meter, Second, Unitless};
+    use typenum::{N1, P2, P4};
+
+    #[test]
+    fn fractional_exponents() {
+        let _: Reduced<Frac<P2, P4>> = Frac::<P1, P2>(PhantomData);
+        let _: Reduced<Frac<Z0, P4>> = Frac::<Z0, P1>(PhantomData);
+
+        let root_hz = (Unitless::new(4) / Second::new(1)).sqrt();
+        assert_eq!(format!("{:?}", root_hz), "2.0 Seconds^-1/2");
+        assert_eq!(root_hz * root_hz, Unitless::new(4) / Second::new(1));
+        assert_eq!(Meter::new(9).sqrt().powi::<P2>(), Meter::new(9));
+        assert_eq!(root_hz.powi::<N1>().powi::<P2>(), Second::new(0.25));
+    }
+}
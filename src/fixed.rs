@@ -0,0 +1,287 @@
+//! Fixed-point numbers in Q-format, for targets without (fast) floating point.
+//!
+//! `Fixed<FRAC>` stores `value * 2^FRAC` in an `i32`, so `Fixed<16>` is Q16.16. Prefix
+//! conversions scale by exact integer powers of ten and round with the mode `R`. Results that
+//! don't fit saturate at the smallest or largest representable value. Only units outside the
+//! decimal SI family (see `Unit::factor`) go through floating point.
+
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::UnitValue;
+
+/// How the result of a division is rounded to an integer
+pub trait Rounding: Copy {
+    /// `n / d` rounded, `d` is positive
+    fn divide(n: i128, d: i128) -> i128;
+}
+
+/// Round to nearest, ties away from zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Nearest;
+/// Round to nearest, ties to even
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NearestEven;
+/// Round toward negative infinity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Floor;
+/// Round toward positive infinity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ceil;
+/// Truncate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TowardZero;
+
+impl Rounding for Nearest {
+    fn divide(n: i128, d: i128) -> i128 {
+        if n >= 0 {
+            (n + d / 2) / d
+        } else {
+            (n - d / 2) / d
+        }
+    }
+}
+
+impl Rounding for NearestEven {
+    fn divide(n: i128, d: i128) -> i128 {
+        let (q, r) = (n.div_euclid(d), n.rem_euclid(d));
+        match (2 * r).cmp(&d) {
+            Ordering::Less => q,
+            Ordering::Greater => q + 1,
+            Ordering::Equal => q + (q & 1),
+        }
+    }
+}
+
+impl Rounding for Floor {
+    fn divide(n: i128, d: i128) -> i128 {
+        n.div_euclid(d)
+    }
+}
+
+impl Rounding for Ceil {
+    fn divide(n: i128, d: i128) -> i128 {
+        -(-n).div_euclid(d)
+    }
+}
+
+impl Rounding for TowardZero {
+    fn divide(n: i128, d: i128) -> i128 {
+        n / d
+    }
+}
+
+/// A signed fixed-point number with `FRAC` fractional bits, rounding with `R`
+pub struct Fixed<const FRAC: u32, R = Nearest>(i32, PhantomData<R>);
+
+/// Q16.16, 16 integer and 16 fractional bits
+pub type Q16 = Fixed<16>;
+/// Q1.31, for values in `[-1, 1)`
+pub type Q31 = Fixed<31>;
+
+impl<const FRAC: u32, R> Clone for Fixed<FRAC, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<const FRAC: u32, R> Copy for Fixed<FRAC, R> {}
+
+impl<const FRAC: u32, R> PartialEq for Fixed<FRAC, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<const FRAC: u32, R> Eq for Fixed<FRAC, R> {}
+
+impl<const FRAC: u32, R> PartialOrd for Fixed<FRAC, R> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<const FRAC: u32, R> Ord for Fixed<FRAC, R> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<const FRAC: u32, R> std::fmt::Debug for Fixed<FRAC, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fixed<{}>({})", FRAC, self.to_f64())
+    }
+}
+
+impl<const FRAC: u32, R> Fixed<FRAC, R> {
+    /// The raw value of 1, an `i64` since Q1.31 can't represent 1 in its `i32`
+    pub const ONE: i64 = 1 << FRAC;
+    pub const MIN: Self = Self(i32::MIN, PhantomData);
+    pub const MAX: Self = Self(i32::MAX, PhantomData);
+
+    /// The number `raw / 2^FRAC`
+    pub const fn from_raw(raw: i32) -> Self {
+        Self(raw, PhantomData)
+    }
+
+    pub const fn raw(&self) -> i32 {
+        self.0
+    }
+
+    /// Saturates if `value` doesn't fit
+    pub fn from_int(value: i32) -> Self {
+        Self::saturate((value as i128) << FRAC)
+    }
+
+    fn saturate(raw: i128) -> Self {
+        Self::from_raw(raw.clamp(i32::MIN as i128, i32::MAX as i128) as i32)
+    }
+
+    /// For host side tooling, e.g. formatting
+    pub fn to_f64(&self) -> f64 {
+        self.0 as f64 / (1u64 << FRAC) as f64
+    }
+}
+
+impl<const FRAC: u32, R: Rounding> Fixed<FRAC, R> {
+    /// `numerator / denominator`, rounded. Panics if `denominator` is zero.
+    pub fn from_ratio(numerator: i32, denominator: i32) -> Self {
+        let (n, d) = ((numerator as i128) << FRAC, denominator as i128);
+        Self::saturate(R::divide(n * d.signum(), d.abs()))
+    }
+
+    /// Rounds `value` to the nearest representable number
+    pub fn from_f64(value: f64) -> Self {
+        Self::saturate((value * (1u64 << FRAC) as f64).round() as i128)
+    }
+
+    /// The same number with different rounding
+    pub fn with_rounding<S: Rounding>(self) -> Fixed<FRAC, S> {
+        Fixed::from_raw(self.0)
+    }
+}
+
+impl<const FRAC: u32, R: Rounding> UnitValue for Fixed<FRAC, R> {
+    fn apply_prefix(&self, power_of_ten: i32) -> Self {
+        let raw = self.0 as i128;
+        match power_of_ten {
+            0 => *self,
+            // Anything that overflows an i128 saturates anyway
+            _ if power_of_ten > 0 => 10i128
+                .checked_pow(power_of_ten as u32)
+                .and_then(|scale| raw.checked_mul(scale))
+                .map_or_else(|| Self::saturate(raw.signum() * i128::MAX), Self::saturate),
+            // 10^38 is larger than any raw value, so the result only depends on the rounding
+            _ => Self::saturate(R::divide(raw, 10i128.pow((-power_of_ten).min(38) as u32))),
+        }
+    }
+
+    fn apply_factor(&self, factor: f64) -> Self {
+        Self::from_f64(self.to_f64() * factor)
+    }
+}
+
+impl<const FRAC: u32, R> Add for Fixed<FRAC, R> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::from_raw(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl<const FRAC: u32, R> Sub for Fixed<FRAC, R> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_raw(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl<const FRAC: u32, R: Rounding> Mul for Fixed<FRAC, R> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::saturate(R::divide(self.0 as i128 * rhs.0 as i128, 1 << FRAC))
+    }
+}
+
+/// Panics if `rhs` is zero
+impl<const FRAC: u32, R: Rounding> Div for Fixed<FRAC, R> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let n = (self.0 as i128) << FRAC;
+        let d = rhs.0 as i128;
+        Self::saturate(R::divide(n * d.signum(), d.abs()))
+    }
+}
+
+impl<const FRAC: u32, R> Neg for Fixed<FRAC, R> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_raw(self.0.saturating_neg())
+    }
+}
+
+impl<const FRAC: u32, R> Display for Fixed<FRAC, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.to_f64(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::Ampere,
+        prefix::{Kilo, Micro, Milli},
+    };
+    use typenum::Z0;
+
+    #[test]
+    fn prefixes() {
+        let current = Milli::<Ampere>::new(Q16::from_int(1500));
+        let amps = current.convert::<Z0, Ampere>();
+        assert_eq!(*amps.value(), Q16::from_ratio(3, 2));
+        assert_eq!(amps.value().raw(), 98304);
+        assert_eq!(amps.convert::<typenum::P3, Ampere>(), current);
+        // 1.5e6 doesn't fit in Q16.16
+        assert_eq!(*current.convert::<typenum::P6, Ampere>().value(), Q16::MAX);
+        assert_eq!(
+            Micro::<Ampere>::new(Q16::from_int(1)),
+            Kilo::<Ampere>::new(Q16::from_raw(0))
+        );
+        for power in [9, 28, 29, 38, 39, 100] {
+            assert_eq!(Q16::MAX.apply_prefix(power), Q16::MAX);
+            assert_eq!(Q16::MIN.apply_prefix(power), Q16::MIN);
+            assert_eq!(Q16::from_raw(0).apply_prefix(power), Q16::from_raw(0));
+        }
+        assert_eq!(Q16::ONE, 65536);
+        assert_eq!(Q31::ONE, 1 << 31);
+
+        let sum = current + amps;
+        assert_eq!(*sum.value(), Q16::from_int(3));
+        let power = amps * amps;
+        assert_eq!(*power.value(), Q16::from_ratio(9, 4));
+    }
+
+    #[test]
+    fn rounding() {
+        // 1 mA is 65.536 raw units of Q16.16 amperes
+        fn raw<R: Rounding>(milliamps: i32) -> i32 {
+            let current = Milli::<Ampere>::new(Fixed::<16, R>::from_int(milliamps));
+            current.convert::<Z0, Ampere>().value().raw()
+        }
+        assert_eq!(raw::<Nearest>(1), 66);
+        assert_eq!(raw::<Nearest>(-1), -66);
+        assert_eq!(raw::<Floor>(1), 65);
+        assert_eq!(raw::<Floor>(-1), -66);
+        assert_eq!(raw::<Ceil>(-1), -65);
+        assert_eq!(raw::<TowardZero>(-1), -65);
+        assert_eq!(NearestEven::divide(5, 2), 2);
+        assert_eq!(NearestEven::divide(7, 2), 4);
+        assert_eq!(Nearest::divide(5, 2), 3);
+        assert_eq!(
+            Q16::from_ratio(1, 3) * Q16::from_int(3),
+            Q16::from_raw(65535)
+        );
+        assert_eq!(Q16::from_int(1) / Q16::from_int(-4), -Q16::from_ratio(1, 4));
+    }
+}
@@ -0,0 +1,190 @@
+//! Gaussian CGS electromagnetic units: statcoulomb, statampere, statvolt and gauss.
+//!
+//! Gaussian units have no base quantity for current. Charge is defined through Coulomb's law
+//! without a `4π ε0`, which gives it the dimension `g^(1/2) cm^(3/2) s^-1`, and the electric and
+//! magnetic fields share a dimension. Converting from SI is therefore not a change of unit
+//! within a dimension but a change of dimension, which `Electromagnetic` describes for each SI
+//! quantity.
+
+use std::f64::consts::PI;
+
+use typenum::{Integer, ToInt, N1, N2, P1, P2, P3, Z0};
+
+use crate::{
+    base::Coherent,
+    dimension::{self, Dim, Dimension, Frac},
+    quantity::DynDimension,
+    system::{SystemUnit, UnitSystem},
+    Unit, Value,
+};
+
+/// The vacuum permittivity in `F/m` (CODATA 2018)
+pub const EPSILON_0: f64 = 8.8541878128e-12;
+/// The speed of light in `m/s`
+pub const C: f64 = 299_792_458.0;
+
+type Zero = Frac<Z0, P1>;
+type Mechanical<L, T, M> = Dim<L, T, M, Zero, Zero, Zero, Zero>;
+
+/// `g^(1/2) cm^(3/2) s^-1`, the statcoulomb. Magnetic flux (the maxwell) has the same dimension.
+pub type GaussianCharge = Mechanical<Frac<P3, P2>, Frac<N1, P1>, Frac<P1, P2>>;
+/// `g^(1/2) cm^(3/2) s^-2`, the statampere
+pub type GaussianCurrent = Mechanical<Frac<P3, P2>, Frac<N2, P1>, Frac<P1, P2>>;
+/// `g^(1/2) cm^(1/2) s^-1`, the statvolt
+pub type GaussianPotential = Mechanical<Frac<P1, P2>, Frac<N1, P1>, Frac<P1, P2>>;
+/// `g^(1/2) cm^(-1/2) s^-1`, the gauss. The electric field (statvolt per centimetre) and the
+/// magnetic field strength (the oersted) have the same dimension.
+pub type GaussianField = Mechanical<Frac<N1, P2>, Frac<N1, P1>, Frac<P1, P2>>;
+
+/// The Gaussian system: centimetre, gram and second, with electromagnetic quantities in
+/// mechanical dimensions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gaussian;
+
+impl UnitSystem for Gaussian {
+    const BASE_UNITS: [f64; 7] = <crate::system::Cgs as UnitSystem>::BASE_UNITS;
+    const SYMBOLS: [&'static str; 7] = <crate::system::Cgs as UnitSystem>::SYMBOLS;
+    const NAMED: &'static [(DynDimension, &'static str)] = &[
+        (DynDimension::from_halves([3, -2, 1, 0, 0, 0, 0]), "statC"),
+        (DynDimension::from_halves([3, -4, 1, 0, 0, 0, 0]), "statA"),
+        (DynDimension::from_halves([1, -2, 1, 0, 0, 0, 0]), "statV"),
+        (DynDimension::from_halves([-1, -2, 1, 0, 0, 0, 0]), "G"),
+        (DynDimension::from_ints([1, -2, 1, 0, 0, 0, 0]), "dyn"),
+        (DynDimension::from_ints([2, -2, 1, 0, 0, 0, 0]), "erg"),
+    ];
+}
+
+pub type Statcoulomb = SystemUnit<Gaussian, GaussianCharge>;
+pub type Statampere = SystemUnit<Gaussian, GaussianCurrent>;
+pub type Statvolt = SystemUnit<Gaussian, GaussianPotential>;
+pub type Gauss = SystemUnit<Gaussian, GaussianField>;
+
+/// An SI electromagnetic dimension, and the dimension of the same quantity in Gaussian units.
+///
+/// A value with a current exponent of `n` converts by a factor of `(4π ε0)^(-n/2)`, times
+/// `c^C` and `(4π)^FOUR_PI` for the magnetic quantities, whose Gaussian units are tied to the
+/// electric ones through the speed of light.
+pub trait Electromagnetic: Dimension {
+    type GaussianDimension: Dimension;
+    const C: i32 = 0;
+    const FOUR_PI: i32 = 0;
+}
+
+impl Electromagnetic for dimension::Charge {
+    type GaussianDimension = GaussianCharge;
+}
+
+impl Electromagnetic for dimension::Current {
+    type GaussianDimension = GaussianCurrent;
+}
+
+impl Electromagnetic for dimension::Voltage {
+    type GaussianDimension = GaussianPotential;
+}
+
+impl Electromagnetic for dimension::ElectricField {
+    type GaussianDimension = GaussianField;
+}
+
+/// Capacitance is a length in Gaussian units
+impl Electromagnetic for dimension::Capacitance {
+    type GaussianDimension = dimension::Length;
+}
+
+/// Resistance is an inverse speed in Gaussian units
+impl Electromagnetic for dimension::Resistance {
+    type GaussianDimension = Mechanical<Frac<N1, P1>, Frac<P1, P1>, Zero>;
+}
+
+impl Electromagnetic for dimension::MagneticFluxDensity {
+    type GaussianDimension = GaussianField;
+    const C: i32 = 1;
+}
+
+impl Electromagnetic for dimension::MagneticFlux {
+    type GaussianDimension = GaussianCharge;
+    const C: i32 = 1;
+}
+
+impl Electromagnetic for dimension::MagneticFieldStrength {
+    type GaussianDimension = GaussianField;
+    const C: i32 = -1;
+    const FOUR_PI: i32 = 1;
+}
+
+/// The Gaussian value of one coherent SI unit of `D`, in coherent SI units of the Gaussian
+/// dimension
+fn factor<D: Electromagnetic>() -> f64 {
+    let (current, _) = D::exponents()[4];
+    (4.0 * PI * EPSILON_0).powf(-current as f64 / 2.0) * C.powi(D::C) * (4.0 * PI).powi(D::FOUR_PI)
+}
+
+/// The Gaussian unit a value of `U` converts into
+pub type GaussianUnit<U> =
+    SystemUnit<Gaussian, <<U as Unit>::Dimension as Electromagnetic>::GaussianDimension>;
+
+impl<P: Integer + ToInt<i32>, U: Unit, K> Value<f64, P, U, K>
+where
+    U::Dimension: Electromagnetic,
+{
+    /// Expresses this SI value in the coherent Gaussian unit of the same quantity, e.g. tesla in
+    /// gauss
+    pub fn to_gaussian(&self) -> Value<f64, Z0, GaussianUnit<U>, K> {
+        let si = *self.to_base().value() * factor::<U::Dimension>();
+        Value::<f64, Z0, Coherent<<U::Dimension as Electromagnetic>::GaussianDimension>, K>::new(si)
+            .convert()
+    }
+
+    /// Reverses `to_gaussian`, from any unit of the Gaussian dimension
+    pub fn from_gaussian<GP, GU>(value: Value<f64, GP, GU, K>) -> Self
+    where
+        GP: Integer + ToInt<i32>,
+        GU: Unit<Dimension = <U::Dimension as Electromagnetic>::GaussianDimension>,
+    {
+        let si = *value.to_base().value() / factor::<U::Dimension>();
+        Self::from_base(Value::new(si))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Ampere, Meter, Second, Unitless},
+        close,
+        prefix::Milli,
+    };
+
+    #[test]
+    fn conversions() {
+        let coulomb = Ampere::new(1f64) * Second::new(1f64);
+        let statc = coulomb.to_gaussian();
+        assert!(close(*statc.value(), 2.99792458e9, 1e-9));
+        assert_eq!(format!("{:?}", statc), format!("{:?} statC", statc.value()));
+        let back = Value::<f64, Z0, Coherent<dimension::Charge>>::from_gaussian(statc);
+        assert!(close(*back.value(), 1.0, 1e-9));
+
+        // T = kg s^-2 A^-1, 1 T = 10^4 G
+        let tesla = (Unitless::new(1000f64) * crate::base::Gram::new(1f64))
+            / (Second::new(1f64) * Second::new(1f64) * Ampere::new(1f64));
+        assert!(close(*tesla.to_gaussian().value(), 1e4, 1e-9));
+        let oersted = (Ampere::new(1f64) / Meter::new(1f64)).to_gaussian();
+        assert!(close(*oersted.value(), 4.0 * PI * 1e-3, 1e-9));
+
+        let volt = Value::<f64, Z0, Coherent<dimension::Voltage>>::new(299.792458);
+        let statvolt: Value<f64, Z0, Statvolt> = volt.to_gaussian();
+        assert!(close(*statvolt.value(), 1.0, 1e-9));
+        // A picofarad is a bit less than a centimetre
+        let picofarad = Value::<f64, typenum::P12, Coherent<dimension::Capacitance>>::new(1.0);
+        assert!(close(*picofarad.to_gaussian().value(), 0.8987551787, 1e-9));
+        // The electric field shares the dimension of the magnetic field
+        let field: Value<f64, Z0, Gauss> =
+            Value::<f64, Z0, Coherent<dimension::ElectricField>>::new(1.0).to_gaussian();
+        assert!(close(*field.value(), 1.0 / 29979.2458, 1e-9));
+        assert!(close(
+            *Milli::<Ampere>::new(1f64).to_gaussian().value(),
+            2.99792458e6,
+            1e-9
+        ));
+    }
+}
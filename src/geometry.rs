@@ -0,0 +1,220 @@
+//! Fixed-size vectors of values sharing one unit, e.g. positions, velocities and forces.
+//!
+//! A `Vector3<Value<f64, Z0, Meter>>` adds and subtracts like its components, `dot` and `cross`
+//! multiply the units like `Value` multiplication does (a length times a force is an energy or a
+//! torque), and `norm` stays in the unit of the components.
+
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+
+use typenum::{Integer, ToInt};
+
+use crate::{math::Float, Unit, UnitValue, Value};
+
+/// `N` components of type `T`, usually a `Value`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vector<T, const N: usize>(pub [T; N]);
+
+pub type Vector2<T> = Vector<T, 2>;
+pub type Vector3<T> = Vector<T, 3>;
+
+impl<T> Vector<T, 2> {
+    pub fn new(x: T, y: T) -> Self {
+        Self([x, y])
+    }
+}
+
+impl<T> Vector<T, 3> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self([x, y, z])
+    }
+}
+
+impl<T, const N: usize> Vector<T, N> {
+    /// Applies `f` to every component
+    pub fn map<R>(self, f: impl FnMut(T) -> R) -> Vector<R, N> {
+        Vector(self.0.map(f))
+    }
+
+    /// The sum of the component-wise products, in the product unit of the components
+    pub fn dot<R>(&self, rhs: &Vector<R, N>) -> T::Output
+    where
+        T: Clone + Mul<R>,
+        R: Clone,
+        T::Output: Add<Output = T::Output>,
+    {
+        self.0
+            .iter()
+            .zip(&rhs.0)
+            .map(|(a, b)| a.clone() * b.clone())
+            .reduce(|sum, product| sum + product)
+            .expect("dot product of empty vectors")
+    }
+}
+
+impl<T: Clone> Vector<T, 3> {
+    /// The cross product, in the product unit of the components
+    pub fn cross<R: Clone>(&self, rhs: &Vector<R, 3>) -> Vector<T::Output, 3>
+    where
+        T: Mul<R>,
+        T::Output: Sub<Output = T::Output>,
+    {
+        let (a, b) = (&self.0, &rhs.0);
+        let product =
+            |i: usize, j: usize| a[i].clone() * b[j].clone() - a[j].clone() * b[i].clone();
+        Vector([product(1, 2), product(2, 0), product(0, 1)])
+    }
+}
+
+impl<V, P: Integer, U: Unit, K, const N: usize> Vector<Value<V, P, U, K>, N> {
+    /// Wraps plain numbers, e.g. a `[f64; 3]`, in the unit of the vector
+    pub fn from_values(values: [V; N]) -> Self {
+        Vector(values.map(Value::new))
+    }
+
+    /// The plain numbers in the unit of the vector
+    pub fn values(self) -> [V; N] {
+        self.0.map(|component| component.0)
+    }
+}
+
+impl<V: UnitValue, P: Integer + ToInt<i32>, U: Unit, K, const N: usize>
+    Vector<Value<V, P, U, K>, N>
+{
+    /// Converts every component, see `Value::convert`
+    pub fn convert<TargetP: Integer + ToInt<i32>, TargetU: Unit<Dimension = U::Dimension>>(
+        &self,
+    ) -> Vector<Value<V, TargetP, TargetU, K>, N> {
+        Vector(self.0.each_ref().map(Value::convert))
+    }
+}
+
+impl<V: Float, P: Integer, U: Unit, K, const N: usize> Vector<Value<V, P, U, K>, N> {
+    /// The Euclidean length, in the unit of the components
+    pub fn norm(&self) -> Value<V, P, U, K> {
+        let mut components = self.0.iter().map(|component| component.0.clone());
+        let first = components.next().expect("norm of an empty vector");
+        Value::new(components.fold(first.abs(), Float::hypot))
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(components: [T; N]) -> Self {
+        Self(components)
+    }
+}
+
+/// Component-wise, in the units of `rhs` like `Value` addition
+impl<T: Add<R>, R, const N: usize> Add<Vector<R, N>> for Vector<T, N> {
+    type Output = Vector<T::Output, N>;
+    fn add(self, rhs: Vector<R, N>) -> Self::Output {
+        let mut rhs = IntoIterator::into_iter(rhs.0);
+        self.map(|a| a + rhs.next().unwrap())
+    }
+}
+
+/// Component-wise, in the units of `rhs` like `Value` subtraction
+impl<T: Sub<R>, R, const N: usize> Sub<Vector<R, N>> for Vector<T, N> {
+    type Output = Vector<T::Output, N>;
+    fn sub(self, rhs: Vector<R, N>) -> Self::Output {
+        let mut rhs = IntoIterator::into_iter(rhs.0);
+        self.map(|a| a - rhs.next().unwrap())
+    }
+}
+
+impl<T: Neg, const N: usize> Neg for Vector<T, N> {
+    type Output = Vector<T::Output, N>;
+    fn neg(self) -> Self::Output {
+        self.map(Neg::neg)
+    }
+}
+
+/// Scales every component by a `Value`, e.g. a velocity times a time
+impl<
+        T: Mul<Value<V, P, U, K>>,
+        V: Clone,
+        P: Integer,
+        U: Unit + Clone,
+        K: Clone,
+        const N: usize,
+    > Mul<Value<V, P, U, K>> for Vector<T, N>
+{
+    type Output = Vector<T::Output, N>;
+    fn mul(self, rhs: Value<V, P, U, K>) -> Self::Output {
+        self.map(|a| a * rhs.clone())
+    }
+}
+
+/// Divides every component by a `Value`, e.g. a displacement by a time
+impl<
+        T: Div<Value<V, P, U, K>>,
+        V: Clone,
+        P: Integer,
+        U: Unit + Clone,
+        K: Clone,
+        const N: usize,
+    > Div<Value<V, P, U, K>> for Vector<T, N>
+{
+    type Output = Vector<T::Output, N>;
+    fn div(self, rhs: Value<V, P, U, K>) -> Self::Output {
+        self.map(|a| a / rhs.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Coherent, Meter, Second},
+        dimension::{Energy, Force, Speed},
+        prefix::Milli,
+    };
+    use typenum::{P2, Z0};
+
+    type Meters = Value<f64, Z0, Meter>;
+    type Newtons = Value<f64, Z0, Coherent<Force>>;
+
+    #[test]
+    fn products() {
+        let lever = Vector3::<Meters>::from_values([0.5, 0.0, 0.0]);
+        let force = Vector3::<Newtons>::from_values([0.0, 20.0, 0.0]);
+        assert_eq!(lever.cross(&force).values(), [0.0, 0.0, 10.0]);
+        let work: Value<f64, Z0, Coherent<Energy>> =
+            lever.dot(&Vector3::<Newtons>::from_values([4.0, 1.0, 0.0]));
+        assert_eq!(*work.value(), 2.0);
+        assert_eq!(
+            *Vector2::<Meters>::from_values([3.0, -4.0]).norm().value(),
+            5.0
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let position = Vector3::new(
+            Milli::<Meter>::new(1500.0),
+            Milli::<Meter>::new(0.0),
+            Milli::<Meter>::new(-250.0),
+        );
+        let centimeters = position.convert::<P2, Meter>();
+        assert_eq!(centimeters.values(), [150.0, 0.0, -25.0]);
+        let moved = position + centimeters;
+        assert_eq!(moved.values(), [300.0, 0.0, -50.0]);
+
+        let velocity: Vector3<Value<f64, Z0, Coherent<Speed>>> =
+            moved.convert::<Z0, Meter>() / Value::<f64, Z0, Second>::new(2.0);
+        assert_eq!(velocity.values(), [1.5, 0.0, -0.25]);
+        assert_eq!(*(-velocity[2]).value(), 0.25);
+    }
+}
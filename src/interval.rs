@@ -0,0 +1,248 @@
+//! Closed intervals of real numbers, for carrying rigorous bounds (e.g. a tolerance stack-up)
+//! through `Value` arithmetic.
+//!
+//! Every operation rounds its lower bound down and its upper bound up by one ulp, so the result
+//! contains the exact result of the operation on any numbers from the operands, despite the
+//! rounding of floating point arithmetic.
+
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::UnitValue;
+
+/// The closed interval `[lo, hi]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    lo: f64,
+    hi: f64,
+}
+
+impl Interval {
+    /// Panics if `lo > hi` or either bound is NaN
+    pub fn new(lo: f64, hi: f64) -> Self {
+        assert!(lo <= hi, "empty interval [{}, {}]", lo, hi);
+        Self { lo, hi }
+    }
+
+    /// The interval containing only `value`
+    pub fn point(value: f64) -> Self {
+        Self::new(value, value)
+    }
+
+    /// `nominal ± tolerance`
+    pub fn tolerance(nominal: f64, tolerance: f64) -> Self {
+        Self::new(nominal - tolerance.abs(), nominal + tolerance.abs()).widen()
+    }
+
+    pub const ENTIRE: Self = Self {
+        lo: f64::NEG_INFINITY,
+        hi: f64::INFINITY,
+    };
+
+    pub fn lo(&self) -> f64 {
+        self.lo
+    }
+
+    pub fn hi(&self) -> f64 {
+        self.hi
+    }
+
+    pub fn width(&self) -> f64 {
+        self.hi - self.lo
+    }
+
+    pub fn midpoint(&self) -> f64 {
+        self.lo / 2.0 + self.hi / 2.0
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.lo <= value && value <= self.hi
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.lo <= other.hi && other.lo <= self.hi
+    }
+
+    /// The smallest interval containing both
+    pub fn hull(&self, other: &Self) -> Self {
+        Self::new(self.lo.min(other.lo), self.hi.max(other.hi))
+    }
+
+    /// Rounds outward by one ulp on each side
+    fn widen(self) -> Self {
+        Self {
+            lo: self.lo.next_down(),
+            hi: self.hi.next_up(),
+        }
+    }
+
+    /// The interval spanned by `values`, rounded outward. A NaN, e.g. from `inf / inf`, could
+    /// be anything, so gives `ENTIRE`.
+    fn span(values: [f64; 4]) -> Self {
+        if values.iter().any(|value| value.is_nan()) {
+            return Self::ENTIRE;
+        }
+        let lo = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Self::new(lo, hi).widen()
+    }
+}
+
+impl From<f64> for Interval {
+    fn from(value: f64) -> Self {
+        Self::point(value)
+    }
+}
+
+impl UnitValue for Interval {
+    fn apply_prefix(&self, power_of_ten: i32) -> Self {
+        match power_of_ten {
+            0 => *self,
+            // Powers of ten up to 10^22 are exact in an f64
+            1..=22 => *self * Self::point(10f64.powi(power_of_ten)),
+            // Otherwise the scale itself is rounded, so it is an interval too
+            _ => *self * Self::point(10f64.powi(power_of_ten)).widen(),
+        }
+    }
+
+    /// Factors such as 0.3048 are already rounded, so like large powers of ten they are widened
+    fn apply_factor(&self, factor: f64) -> Self {
+        *self * Self::point(factor).widen()
+    }
+}
+
+impl Add for Interval {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.lo + rhs.lo, self.hi + rhs.hi).widen()
+    }
+}
+
+impl Sub for Interval {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.lo - rhs.hi, self.hi - rhs.lo).widen()
+    }
+}
+
+/// Zero times an infinite bound is zero, as in IEEE 1788, since the bound is only a limit
+impl Mul for Interval {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let product = |a: f64, b: f64| if a == 0.0 || b == 0.0 { 0.0 } else { a * b };
+        Self::span([
+            product(self.lo, rhs.lo),
+            product(self.lo, rhs.hi),
+            product(self.hi, rhs.lo),
+            product(self.hi, rhs.hi),
+        ])
+    }
+}
+
+/// Dividing by an interval containing zero gives `Interval::ENTIRE`
+impl Div for Interval {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        if rhs.contains(0.0) {
+            return Self::ENTIRE;
+        }
+        Self::span([
+            self.lo / rhs.lo,
+            self.lo / rhs.hi,
+            self.hi / rhs.lo,
+            self.hi / rhs.hi,
+        ])
+    }
+}
+
+impl Neg for Interval {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.hi, -self.lo)
+    }
+}
+
+/// One interval is less than another if every number in it is. Overlapping intervals are
+/// unordered, unless they are the same interval.
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.hi < other.lo {
+            Some(Ordering::Less)
+        } else if self.lo > other.hi {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        Display::fmt(&self.lo, f)?;
+        write!(f, ", ")?;
+        Display::fmt(&self.hi, f)?;
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::Meter,
+        prefix::{Micro, Milli},
+        system::Imperial,
+        Value,
+    };
+
+    #[test]
+    fn stack_up() {
+        let part = Milli::<Meter>::new(Interval::new(9.95, 10.05));
+        let stack = part + part + part;
+        assert!(stack.value().contains(29.85) && stack.value().contains(30.15));
+        assert!(stack.value().width() < 0.3 + 1e-12);
+
+        let gap = Milli::<Meter>::new(Interval::point(30.2)) - stack;
+        assert!(gap.value().lo() > 0.0);
+        // 0.1 has no exact binary representation, the bounds still enclose it
+        let meters = Milli::<Meter>::new(Interval::point(0.1)).convert::<typenum::Z0, Meter>();
+        assert!(meters.value().contains(1e-4) && meters.value().width() > 0.0);
+        // Neither is 1 / 0.3048 ft
+        let feet =
+            Value::<_, typenum::Z0, Meter>::new(Interval::point(1.0)).to_system::<Imperial>();
+        assert!(feet.value().contains(1.0 / 0.3048) && feet.value().width() > 0.0);
+
+        assert!(part < Milli::<Meter>::new(Interval::new(10.1, 10.2)));
+        assert!(part > Micro::<Meter>::new(Interval::new(9000.0, 9900.0)));
+        let overlapping = Milli::<Meter>::new(Interval::new(10.0, 10.1));
+        assert_eq!(part.partial_cmp(&overlapping), None);
+        assert!(part == part);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Interval::new(-1.0, 2.0);
+        let b = Interval::new(3.0, 4.0);
+        let product = a * b;
+        assert!(product.contains(-4.0) && product.contains(8.0));
+        assert!(product.lo() > -4.0001 && product.hi() < 8.0001);
+        assert!((b / a).lo().is_infinite());
+        let zero = Interval::point(0.0);
+        assert!((zero * Interval::ENTIRE).contains(0.0));
+        assert!((zero * Interval::ENTIRE).width() < 1e-300);
+        assert!((b / a * zero).contains(0.0));
+        assert_eq!(
+            Interval::ENTIRE / Interval::new(1.0, f64::INFINITY),
+            Interval::ENTIRE
+        );
+        assert!((a / b).contains(-1.0 / 3.0) && (a / b).contains(2.0 / 3.0));
+        assert_eq!(-a, Interval::new(-2.0, 1.0));
+        assert_eq!(format!("{:.1}", b), "[3.0, 4.0]");
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::Debug;
+
+/// Tags a `Value` with the kind of quantity it holds, on top of its unit.
+///
+/// Some quantities share a dimension without meaning the same thing: a torque and an energy are
+/// both `kg * m^2 / s^2`, an activity and a frequency are both `1 / s`. Values of different
+/// kinds can't be added, subtracted, compared or converted into each other, even when their
+/// units match. `Value::reinterpret_kind` changes the kind when that is really intended.
+pub trait Kind: Copy + Debug {}
+
+/// The kind of a `Value` which hasn't been tagged. This is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnyKind;
+
+/// The kind of the product (or quotient) of values of kind `Self` and `Rhs`.
+///
+/// An untagged factor keeps the kind of the other side, so scaling a torque by a plain number
+/// is still a torque. The product of two tagged values is untagged.
+pub trait CombineKind<Rhs> {
+    type Output;
+}
+
+impl CombineKind<AnyKind> for AnyKind {
+    type Output = AnyKind;
+}
+
+impl<K: Kind> CombineKind<AnyKind> for K {
+    type Output = K;
+}
+
+impl<K: Kind> CombineKind<K> for AnyKind {
+    type Output = K;
+}
+
+impl<K: Kind, Rhs: Kind> CombineKind<Rhs> for K {
+    type Output = AnyKind;
+}
+
+macro_rules! kind {
+    ($($(#[$doc:meta])* $name:ident),+) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name;
+            impl Kind for $name {}
+        )+
+    };
+}
+
+kind!(
+    /// A moment of force, as opposed to an energy
+    Torque,
+    /// Decays per second of a radioactive source, as opposed to a frequency
+    Activity,
+    /// Radiation dose weighted by its biological effect, as opposed to an absorbed dose
+    EquivalentDose
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Meter, Second, Unitless},
+        prefix::Kilo,
+        Value,
+    };
+    use typenum::Z0;
+
+    #[test]
+    fn kinds() {
+        let lever = Meter::new(2);
+        let torque = (lever * Meter::new(3)).reinterpret_kind::<Torque>();
+        let scaled = torque * Unitless::new(2);
+        let _: Value<_, Z0, _, Torque> = scaled;
+        assert_eq!(scaled, (Meter::new(6) * Meter::new(2)).reinterpret_kind::<Torque>());
+        assert_eq!(
+            scaled.reinterpret_kind::<AnyKind>(),
+            Kilo::<Meter>::new(0.012f64) * Meter::new(1)
+        );
+
+        let activity = (Unitless::new(5) / Second::new(1)).reinterpret_kind::<Activity>();
+        let _: Value<_, Z0, _, AnyKind> = activity * activity;
+        assert!(activity > (Unitless::new(4) / Second::new(1)).reinterpret_kind::<Activity>());
+    }
+}
@@ -1,59 +1,186 @@
 #![cfg_attr(nightly, feature(trait_specialization))]
 pub mod base;
+pub mod calculus;
+pub mod cast;
+pub mod codes;
+pub mod complex;
+pub mod defs;
+pub mod dimension;
+pub mod fixed;
+pub mod gaussian;
+pub mod geometry;
+pub mod interval;
+pub mod kind;
+pub mod math;
+pub mod measurement;
+pub mod natural;
+pub mod ode;
+pub mod optimize;
+pub mod parse;
 pub mod prefix;
+pub mod quantities;
+pub mod quantity;
+#[cfg(feature = "bigrational")]
+pub mod rational;
+pub mod registry;
+pub mod session;
+pub mod system;
+pub mod ucum;
+pub mod units_dat;
 
-use std::{fmt::Debug, marker::PhantomData, ops::{Div, Add, Sub, Mul}};
+use std::{fmt::Debug, marker::PhantomData, ops::{Div, Add, Sub, Mul, Neg}};
 
-use base::Unitless;
+use base::Coherent;
+use kind::{AnyKind, CombineKind};
 use prefix::Prefix;
 use private::Sealed;
-use typenum::{Integer, ToInt, op};
+use typenum::{Diff, Integer, Sum, ToInt, Z0, op};
 
 mod private {
     pub trait Sealed {}
     impl<T> Sealed for T {}
 }
 
+/// Whether two floating point results agree to within the relative error `tolerance`, for the
+/// tests
+#[cfg(test)]
+pub(crate) fn close(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance * a.abs().max(b.abs())
+}
+
 pub trait Unit: Copy + Sealed {
-    type Base;
+    /// The coherent SI unit this unit is measured against, always `base::Coherent<Self::Dimension>`
+    type Base: Unit<Dimension = Self::Dimension>;
+    /// The exponents of the base quantities this unit measures, see `dimension::Dim`. Units with
+    /// the same `Dimension` can be converted into each other.
+    type Dimension;
+    /// The power of ten between this unit and `Base`, following the same convention as the prefix
+    /// of a `Value`: one unit is `10^-SCALE` of `Base`.
+    const SCALE: i32 = 0;
     fn create() -> Self;
+    /// The part of the size of this unit that isn't a power of ten: one unit is
+    /// `factor() * 10^-SCALE` of `Base`. Only units outside the decimal SI family, such as the
+    /// foot of `system::Imperial`, override this.
+    fn factor() -> f64 {
+        1.0
+    }
     // fn conversion() -> Op;
 }
 pub trait BaseUnit: Unit {}
 
+/// The unit of `Value<_, _, A> * Value<_, _, B>`
+pub type MulUnit<A, B> = Coherent<Sum<<A as Unit>::Dimension, <B as Unit>::Dimension>>;
+/// The unit of `Value<_, _, A> / Value<_, _, B>`
+pub type DivUnit<A, B> = Coherent<Diff<<A as Unit>::Dimension, <B as Unit>::Dimension>>;
+
+/// A value `V` in the unit `U` scaled by the prefix `10^-P`, holding a quantity of kind `K`
+/// (see `kind::Kind`).
 #[derive(Clone, Copy)]
-pub struct Value<V, P: Integer, U: Unit>(V, Prefix<P, U>);
+#[repr(transparent)]
+pub struct Value<V, P: Integer, U: Unit, K = AnyKind>(V, Prefix<P, U>, PhantomData<K>);
 
-impl<V, P: Integer, U: Unit> Value<V, P, U> {
+impl<V, P: Integer, U: Unit, K> Value<V, P, U, K> {
     pub fn new(v: V) -> Self {
-        Self(v, Prefix(U::create(), PhantomData))
+        Self(v, Prefix(PhantomData, PhantomData), PhantomData)
     }
 
     pub fn value(&self) -> &V {
         &self.0
     }
+
+    /// Changes the kind of quantity this value holds, keeping the value and unit.
+    ///
+    /// ```compile_fail
+    /// # use units::{base::Meter, kind::Torque};
+    /// let torque = (Meter::new(1) * Meter::new(2)).reinterpret_kind::<Torque>();
+    /// let energy = Meter::new(1) * Meter::new(2);
+    /// let _ = torque + energy;
+    /// ```
+    pub fn reinterpret_kind<NewK>(self) -> Value<V, P, U, NewK> {
+        Value(self.0, self.1, PhantomData)
+    }
 }
 
-pub trait UnitValue: Copy {
+pub trait UnitValue: Clone {
     fn apply_prefix(&self, power_of_ten: i32) -> Self;
+    /// Multiplies by a factor that isn't a power of ten, for the units outside the decimal SI
+    /// family (see `Unit::factor`), e.g. the foot of `system::Imperial`.
+    ///
+    /// This method is new in 0.2.0 and has no default, so implementations written against 0.1
+    /// have to add it. Types without an inexact multiplication can go through `f64`, as
+    /// `fixed::Fixed` does.
+    fn apply_factor(&self, factor: f64) -> Self;
+
+    /// Multiplies by `factor * 10^power_of_ten`, leaving the value untouched by a factor of one
+    fn rescale(&self, power_of_ten: i32, factor: f64) -> Self {
+        let scaled = self.apply_prefix(power_of_ten);
+        if factor == 1.0 {
+            scaled
+        } else {
+            scaled.apply_factor(factor)
+        }
+    }
+
+    /// `rescale` for every element, which implementations can override to compute the scale once
+    fn rescale_all(values: &mut [Self], power_of_ten: i32, factor: f64) {
+        for value in values {
+            *value = value.rescale(power_of_ten, factor);
+        }
+    }
 }
 
 impl UnitValue for f64 {
     fn apply_prefix(&self, power_of_ten: i32) -> Self {
         self * 10f64.powi(power_of_ten)
     }
+
+    fn apply_factor(&self, factor: f64) -> Self {
+        self * factor
+    }
+
+    fn rescale_all(values: &mut [Self], power_of_ten: i32, factor: f64) {
+        let scale = 10f64.powi(power_of_ten) * factor;
+        for value in values {
+            *value *= scale;
+        }
+    }
+}
+
+/// Scales in `f32` except in `rescale_all`, which combines the prefix and factor in `f64` first
+impl UnitValue for f32 {
+    fn apply_prefix(&self, power_of_ten: i32) -> Self {
+        self * 10f32.powi(power_of_ten)
+    }
+
+    fn apply_factor(&self, factor: f64) -> Self {
+        self * factor as f32
+    }
+
+    fn rescale_all(values: &mut [Self], power_of_ten: i32, factor: f64) {
+        let scale = (10f64.powi(power_of_ten) * factor) as f32;
+        for value in values {
+            *value *= scale;
+        }
+    }
 }
 
 // TODO: create and use a different `From/Into` trait
-impl<V: UnitValue, P: Integer + ToInt<i32>, U: Unit> Value<V, P, U> {
-    pub fn convert<TargetP: Integer + ToInt<i32>, TargetU: Unit>(
+impl<V: UnitValue, P: Integer + ToInt<i32>, U: Unit, K> Value<V, P, U, K> {
+    pub fn convert<TargetP: Integer + ToInt<i32>, TargetU: Unit<Dimension = U::Dimension>>(
         &self,
-    ) -> Value<V, TargetP, TargetU>
-    where
-        U: Div<TargetU, Output = Unitless>,
-    {
-        let tmp = Prefix(TargetU::create(), PhantomData);
-        Value(self.1.convert(self.0, &tmp), tmp)
+    ) -> Value<V, TargetP, TargetU, K> {
+        let tmp = Prefix(PhantomData, PhantomData);
+        Value(self.1.convert(self.0.clone(), &tmp), tmp, PhantomData)
+    }
+
+    /// Expresses this value in the coherent SI unit of its dimension, without a prefix
+    pub fn to_base(&self) -> Value<V, Z0, U::Base, K> {
+        self.convert()
+    }
+
+    /// Reverses `to_base`
+    pub fn from_base(base: Value<V, Z0, U::Base, K>) -> Self {
+        base.convert()
     }
 }
 
@@ -83,12 +210,13 @@ impl<
         RhsP: Integer + ToInt<i32>,
         LhsU: Unit,
         RhsU: Unit,
-    > PartialEq<Value<RhsV, RhsP, RhsU>> for Value<LhsV, LhsP, LhsU>
+        K,
+    > PartialEq<Value<RhsV, RhsP, RhsU, K>> for Value<LhsV, LhsP, LhsU, K>
 where
     LhsV: PartialEq<RhsV>,
-    LhsU: Div<RhsU, Output = Unitless>,
+    RhsU: Unit<Dimension = LhsU::Dimension>,
 {
-    fn eq(&self, other: &Value<RhsV, RhsP, RhsU>) -> bool {
+    fn eq(&self, other: &Value<RhsV, RhsP, RhsU, K>) -> bool {
         self.convert::<RhsP, RhsU>().value().eq(other.value())
     }
 }
@@ -97,10 +225,10 @@ impl<
         LhsV: UnitValue,
         LhsP: Integer + ToInt<i32>,
         LhsU: Unit,
-    > Eq for Value<LhsV, LhsP, LhsU>
+        K,
+    > Eq for Value<LhsV, LhsP, LhsU, K>
 where
     LhsV: Eq,
-    LhsU: Div<LhsU, Output = Unitless>,
 {}
 
 impl<
@@ -110,12 +238,13 @@ impl<
         RhsP: Integer + ToInt<i32>,
         LhsU: Unit,
         RhsU: Unit,
-    > PartialOrd<Value<RhsV, RhsP, RhsU>> for Value<LhsV, LhsP, LhsU>
+        K,
+    > PartialOrd<Value<RhsV, RhsP, RhsU, K>> for Value<LhsV, LhsP, LhsU, K>
 where
     LhsV: PartialOrd<RhsV>,
-    LhsU: Div<RhsU, Output = Unitless>,
+    RhsU: Unit<Dimension = LhsU::Dimension>,
 {
-    fn partial_cmp(&self, other: &Value<RhsV, RhsP, RhsU>) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Value<RhsV, RhsP, RhsU, K>) -> Option<std::cmp::Ordering> {
         self.convert::<RhsP, RhsU>().value().partial_cmp(other.value())
     }
 }
@@ -124,12 +253,12 @@ impl<
         LhsV: UnitValue,
         LhsP: Integer + ToInt<i32>,
         LhsU: Unit,
-    > Ord for Value<LhsV, LhsP, LhsU>
+        K,
+    > Ord for Value<LhsV, LhsP, LhsU, K>
 where
     LhsV: Ord,
-    LhsU: Div<LhsU, Output = Unitless>,
 {
-    fn cmp(&self, other: &Value<LhsV, LhsP, LhsU>) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Value<LhsV, LhsP, LhsU, K>) -> std::cmp::Ordering {
         self.convert::<LhsP, LhsU>().value().cmp(other.value())
     }
 }
@@ -141,14 +270,15 @@ impl<
         RhsP: Integer + ToInt<i32>,
         LhsU: Unit,
         RhsU: Unit,
-    > Add<Value<RhsV, RhsP, RhsU>> for Value<LhsV, LhsP, LhsU>
+        K,
+    > Add<Value<RhsV, RhsP, RhsU, K>> for Value<LhsV, LhsP, LhsU, K>
 where
     LhsV: Add<RhsV>,
-    LhsU: Div<RhsU, Output = Unitless>,
+    RhsU: Unit<Dimension = LhsU::Dimension>,
 {
-    type Output = Value<op!(LhsV + RhsV), RhsP, RhsU>;
-    fn add(self, other: Value<RhsV, RhsP, RhsU>) -> Self::Output {
-        Value(self.convert::<RhsP, RhsU>().value().add(other.0), other.1)
+    type Output = Value<op!(LhsV + RhsV), RhsP, RhsU, K>;
+    fn add(self, other: Value<RhsV, RhsP, RhsU, K>) -> Self::Output {
+        Value(self.convert::<RhsP, RhsU>().0.add(other.0), other.1, PhantomData)
     }
 }
 
@@ -159,14 +289,15 @@ impl<
         RhsP: Integer + ToInt<i32>,
         LhsU: Unit,
         RhsU: Unit,
-    > Sub<Value<RhsV, RhsP, RhsU>> for Value<LhsV, LhsP, LhsU>
+        K,
+    > Sub<Value<RhsV, RhsP, RhsU, K>> for Value<LhsV, LhsP, LhsU, K>
 where
     LhsV: Sub<RhsV>,
-    LhsU: Div<RhsU, Output = Unitless>,
+    RhsU: Unit<Dimension = LhsU::Dimension>,
 {
-    type Output = Value<op!(LhsV - RhsV), RhsP, RhsU>;
-    fn sub(self, other: Value<RhsV, RhsP, RhsU>) -> Self::Output {
-        Value(self.convert::<RhsP, RhsU>().value().sub(other.0), other.1)
+    type Output = Value<op!(LhsV - RhsV), RhsP, RhsU, K>;
+    fn sub(self, other: Value<RhsV, RhsP, RhsU, K>) -> Self::Output {
+        Value(self.convert::<RhsP, RhsU>().0.sub(other.0), other.1, PhantomData)
     }
 }
 
@@ -177,17 +308,22 @@ impl<
         RhsP: Integer + ToInt<i32>,
         LhsU: Unit,
         RhsU: Unit,
-    > Mul<Value<RhsV, RhsP, RhsU>> for Value<LhsV, LhsP, LhsU>
+        LhsK: CombineKind<RhsK>,
+        RhsK,
+    > Mul<Value<RhsV, RhsP, RhsU, RhsK>> for Value<LhsV, LhsP, LhsU, LhsK>
 where
     LhsV: Mul<RhsV>,
-    LhsU: Mul<RhsU>,
+    op!(LhsV * RhsV): UnitValue,
     LhsP: Add<RhsP>,
     <LhsP as Add<RhsP>>::Output: Integer,
-    op!(LhsU * RhsU): Unit,
+    LhsU::Dimension: Add<RhsU::Dimension>,
 {
-    type Output = Value<op!(LhsV * RhsV), op!(LhsP + RhsP), op!(LhsU * RhsU)>;
-    fn mul(self, other: Value<RhsV, RhsP, RhsU>) -> Self::Output {
-        Value(self.value().mul(other.0), self.1 * other.1)
+    type Output = Value<op!(LhsV * RhsV), op!(LhsP + RhsP), MulUnit<LhsU, RhsU>, LhsK::Output>;
+    fn mul(self, other: Value<RhsV, RhsP, RhsU, RhsK>) -> Self::Output {
+        Value::new(self.0.mul(other.0).rescale(
+            -(LhsU::SCALE + RhsU::SCALE),
+            LhsU::factor() * RhsU::factor(),
+        ))
     }
 }
 
@@ -198,21 +334,33 @@ impl<
         RhsP: Integer + ToInt<i32>,
         LhsU: Unit,
         RhsU: Unit,
-    > Div<Value<RhsV, RhsP, RhsU>> for Value<LhsV, LhsP, LhsU>
+        LhsK: CombineKind<RhsK>,
+        RhsK,
+    > Div<Value<RhsV, RhsP, RhsU, RhsK>> for Value<LhsV, LhsP, LhsU, LhsK>
 where
     LhsV: Div<RhsV>,
-    LhsU: Div<RhsU>,
+    op!(LhsV / RhsV): UnitValue,
     LhsP: Sub<RhsP>,
     <LhsP as Sub<RhsP>>::Output: Integer,
-    op!(LhsU / RhsU): Unit,
+    LhsU::Dimension: Sub<RhsU::Dimension>,
 {
-    type Output = Value<op!(LhsV / RhsV), op!(LhsP - RhsP), op!(LhsU / RhsU)>;
-    fn div(self, other: Value<RhsV, RhsP, RhsU>) -> Self::Output {
-        Value(self.value().div(other.0), self.1 / other.1)
+    type Output = Value<op!(LhsV / RhsV), op!(LhsP - RhsP), DivUnit<LhsU, RhsU>, LhsK::Output>;
+    fn div(self, other: Value<RhsV, RhsP, RhsU, RhsK>) -> Self::Output {
+        Value::new(self.0.div(other.0).rescale(
+            -(LhsU::SCALE - RhsU::SCALE),
+            LhsU::factor() / RhsU::factor(),
+        ))
     }
 }
 
-impl<V: Debug, P: Integer + ToInt<i32>, U: Unit + Debug> Debug for Value<V, P, U> {
+impl<V: Neg, P: Integer, U: Unit, K> Neg for Value<V, P, U, K> {
+    type Output = Value<V::Output, P, U, K>;
+    fn neg(self) -> Self::Output {
+        Value(self.0.neg(), self.1, PhantomData)
+    }
+}
+
+impl<V: Debug, P: Integer + ToInt<i32>, U: Unit + Debug, K> Debug for Value<V, P, U, K> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?} {:?}", self.0, self.1)
     }
@@ -280,12 +428,18 @@ fn test_from() {
 //     }
 // }
 //
+/// Declares a trait for every unit of the matching `dimension` alias, so functions can be
+/// generic over e.g. any `Length` or any `Speed`
 macro_rules! unit_types {
     ($($name:ident),+) => {
         $(
-            pub trait $name: Unit {
+            pub trait $name: Unit<Dimension = dimension::$name> {
                 type TypedBase: $name;
             }
+
+            impl<U: Unit<Dimension = dimension::$name>> $name for U {
+                type TypedBase = Coherent<dimension::$name>;
+            }
         )+
     };
 }
@@ -298,6 +452,53 @@ unit_types!(
     Tempature,
     LuminousIntesity
 );
+unit_types!(
+    Area,
+    Volume,
+    Frequency,
+    Speed,
+    Acceleration,
+    Momentum,
+    Force,
+    Energy,
+    Power,
+    Pressure,
+    Density,
+    Charge,
+    Voltage,
+    Resistance,
+    Capacitance,
+    Catalytic,
+    ElectricField,
+    MagneticFlux,
+    MagneticFluxDensity,
+    MagneticFieldStrength
+);
+
+#[test]
+fn dimension_generic() {
+    use crate::base::{Gram, Meter, Second, Unitless};
+    use typenum::Z0;
+
+    fn travel_time<L: Length, S: Speed>(
+        d: Value<f64, Z0, L>,
+        v: Value<f64, Z0, S>,
+    ) -> Value<f64, Z0, impl Time> {
+        d / v
+    }
+
+    fn weight<M: Mass, A: Acceleration>(
+        m: Value<f64, Z0, M>,
+        g: Value<f64, Z0, A>,
+    ) -> Value<f64, Z0, impl Force> {
+        m * g
+    }
+
+    let v = Meter::new(10) / Second::new(1);
+    assert!(travel_time(Meter::new(100), v) == Second::new(10));
+    let per_s2 = Unitless::new(1) / (Second::new(1) * Second::new(1));
+    assert!(weight(Gram::new(2), Meter::new(4.5) * per_s2) == Gram::new(9) * Meter::new(1) * per_s2);
+}
 //
 // macro_rules! impl_inheirt {
 //     ($name:ident) => {
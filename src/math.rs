@@ -0,0 +1,239 @@
+use std::ops::{Add, Div, Mul};
+
+use typenum::{Integer, Prod, Quot, Sum, ToInt, P2, P3, Z0};
+
+use crate::{base::Coherent, kind::CombineKind, MulUnit, Unit, UnitValue, Value};
+
+/// Floating point operations needed by the unit aware math methods on `Value`.
+pub trait Float: UnitValue + PartialOrd {
+    fn powi(self, n: i32) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($($ty:ident),*) => {
+        $(
+            impl Float for $ty {
+                fn powi(self, n: i32) -> Self {
+                    $ty::powi(self, n)
+                }
+                fn sqrt(self) -> Self {
+                    $ty::sqrt(self)
+                }
+                fn cbrt(self) -> Self {
+                    $ty::cbrt(self)
+                }
+                fn abs(self) -> Self {
+                    $ty::abs(self)
+                }
+                fn hypot(self, other: Self) -> Self {
+                    $ty::hypot(self, other)
+                }
+                fn mul_add(self, a: Self, b: Self) -> Self {
+                    $ty::mul_add(self, a, b)
+                }
+                fn floor(self) -> Self {
+                    $ty::floor(self)
+                }
+                fn ceil(self) -> Self {
+                    $ty::ceil(self)
+                }
+                fn round(self) -> Self {
+                    $ty::round(self)
+                }
+            }
+        )*
+    };
+}
+impl_float!(f32, f64);
+
+/// The result of raising `Value<V, P, U>` to the power `E`
+pub type Powi<V, P, U, E> = Value<V, Prod<P, E>, Coherent<Prod<<U as Unit>::Dimension, E>>>;
+/// The result of taking the `N`th root of `Value<V, P, U>`
+pub type Root<V, U, N> = Value<V, Z0, Coherent<Quot<<U as Unit>::Dimension, N>>>;
+
+impl<V: UnitValue + PartialOrd, P: Integer + ToInt<i32>, U: Unit, K> Value<V, P, U, K> {
+    /// Returns the smaller of the two values, in the units of `self`
+    pub fn min<OP: Integer + ToInt<i32>, OU: Unit<Dimension = U::Dimension>>(
+        self,
+        other: Value<V, OP, OU, K>,
+    ) -> Self {
+        let other = other.convert::<P, U>();
+        if other.0 < self.0 {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the larger of the two values, in the units of `self`
+    pub fn max<OP: Integer + ToInt<i32>, OU: Unit<Dimension = U::Dimension>>(
+        self,
+        other: Value<V, OP, OU, K>,
+    ) -> Self {
+        let other = other.convert::<P, U>();
+        if other.0 > self.0 {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Restricts `self` to the range `[min, max]`, in the units of `self`
+    pub fn clamp<
+        MinP: Integer + ToInt<i32>,
+        MinU: Unit<Dimension = U::Dimension>,
+        MaxP: Integer + ToInt<i32>,
+        MaxU: Unit<Dimension = U::Dimension>,
+    >(
+        self,
+        min: Value<V, MinP, MinU, K>,
+        max: Value<V, MaxP, MaxU, K>,
+    ) -> Self {
+        self.max(min).min(max)
+    }
+}
+
+impl<V: Float, P: Integer + ToInt<i32>, U: Unit, K> Value<V, P, U, K> {
+    /// Raises both the value and the unit to the power `E`
+    pub fn powi<E: Integer>(self) -> Powi<V, P, U, E>
+    where
+        P: Mul<E>,
+        Prod<P, E>: Integer,
+        U::Dimension: Mul<E>,
+    {
+        let e = E::to_i32();
+        Value::new(self.0.powi(e).rescale(-U::SCALE * e, U::factor().powi(e)))
+    }
+
+    /// Square root of both the value and the unit, without a prefix. The exponents of the unit
+    /// are halved (and may become fractional).
+    pub fn sqrt(self) -> Root<V, U, P2>
+    where
+        U::Dimension: Div<P2>,
+    {
+        Value::new(self.into_coherent().sqrt())
+    }
+
+    /// Cube root of both the value and the unit, without a prefix. The exponents of the unit
+    /// are divided by three (and may become fractional).
+    pub fn cbrt(self) -> Root<V, U, P3>
+    where
+        U::Dimension: Div<P3>,
+    {
+        Value::new(self.into_coherent().cbrt())
+    }
+
+    /// The number in the coherent SI unit of the dimension, without a prefix
+    fn into_coherent(self) -> V {
+        self.0.rescale(-(P::to_int() + U::SCALE), U::factor())
+    }
+
+    /// Absolute value, in the current units
+    pub fn abs(self) -> Self {
+        Value::new(self.0.abs())
+    }
+
+    /// `sqrt(self^2 + other^2)`, in the units of `self`
+    pub fn hypot<OP: Integer + ToInt<i32>, OU: Unit<Dimension = U::Dimension>>(
+        self,
+        other: Value<V, OP, OU, K>,
+    ) -> Self {
+        Value::new(self.0.hypot(other.convert::<P, U>().0))
+    }
+
+    /// `self * a + b`, in the units of `self * a`. The multiply and add round only once, but
+    /// `self` and `b` are rounded when they are rescaled into those units first, unless their
+    /// prefixes and units already match.
+    pub fn mul_add<AP, AU, AK, BP, BU>(
+        self,
+        a: Value<V, AP, AU, AK>,
+        b: Value<V, BP, BU, K::Output>,
+    ) -> Value<V, Sum<P, AP>, MulUnit<U, AU>, K::Output>
+    where
+        K: CombineKind<AK>,
+        AP: Integer + ToInt<i32>,
+        AU: Unit,
+        BP: Integer + ToInt<i32>,
+        BU: Unit<Dimension = Sum<U::Dimension, AU::Dimension>>,
+        P: Add<AP>,
+        Sum<P, AP>: Integer + ToInt<i32>,
+        U::Dimension: Add<AU::Dimension>,
+    {
+        let b = b.convert::<Sum<P, AP>, MulUnit<U, AU>>();
+        Value::new(self
+            .0
+            .rescale(-(U::SCALE + AU::SCALE), U::factor() * AU::factor())
+            .mul_add(a.0, b.0))
+    }
+
+    /// Rounds down, in the current units
+    pub fn floor(self) -> Self {
+        Value::new(self.0.floor())
+    }
+
+    /// Rounds up, in the current units
+    pub fn ceil(self) -> Self {
+        Value::new(self.0.ceil())
+    }
+
+    /// Rounds to the nearest integer, in the current units
+    pub fn round(self) -> Self {
+        Value::new(self.0.round())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        base::{Coherent, Meter, Second},
+        dimension::Area,
+        prefix::{Centi, Kilo, Milli},
+        Value,
+    };
+    use typenum::{P2, P3, Z0};
+
+    #[test]
+    fn powers_and_roots() {
+        let side = Kilo::<Meter>::new(3f64);
+        let area = side.powi::<P2>();
+        assert_eq!(area, Meter::new(9e6) * Meter::new(1));
+        assert_eq!(area.sqrt(), side);
+        let volume = Meter::new(2).powi::<P3>();
+        assert_eq!(volume.cbrt(), Meter::new(2));
+        let speed = (Meter::new(8).powi::<P2>() / Second::new(4).powi::<P2>()).sqrt();
+        assert_eq!(speed, Meter::new(2) / Second::new(1));
+        // Prefixes that don't divide evenly are applied before the root
+        let area = Value::<f64, P3, Coherent<Area>>::new(4e3);
+        assert_eq!(*area.sqrt().value(), 2.0);
+        assert_eq!(*Milli::<Meter>::new(8e9f64).cbrt().value(), 200.0);
+    }
+
+    #[test]
+    fn unit_preserving() {
+        assert_eq!(Meter::new(-2).abs(), Meter::new(2));
+        assert_eq!(Meter::new(1).min(Centi::<Meter>::new(50f64)), Meter::new(0.5));
+        assert_eq!(Meter::new(1).max(Centi::<Meter>::new(50f64)), Meter::new(1));
+        assert_eq!(
+            Meter::new(3).clamp(Milli::<Meter>::new(0f64), Centi::<Meter>::new(150f64)),
+            Meter::new(1.5)
+        );
+        assert_eq!(Meter::new(3).hypot(Centi::<Meter>::new(400f64)), Meter::new(5));
+        assert_eq!(
+            Meter::new(2).mul_add(Meter::new(3), Centi::<Meter>::new(1f64) * Meter::new(100)),
+            Meter::new(7) * Meter::new(1)
+        );
+        assert_eq!(Meter::new(1.5).floor(), Meter::new(1));
+        assert_eq!(Meter::new(1.5).ceil(), Meter::new(2));
+        assert_eq!(Meter::new(1.4).round(), Meter::new(1));
+        let length = Kilo::<Meter>::new(-1.5f32).abs();
+        assert_eq!(*length.convert::<Z0, Meter>().value(), 1500f32);
+    }
+}
@@ -0,0 +1,336 @@
+//! Values with a standard uncertainty, propagated to first order through arithmetic and unit
+//! conversion.
+//!
+//! `Measurement` treats every operand as independent, which is right for separately measured
+//! inputs but overstates the uncertainty of e.g. `x - x`. `Correlated` tracks how a result
+//! depends on each of `N` independent inputs, so such terms cancel.
+
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use typenum::{Integer, ToInt};
+
+use crate::{dimension::Dimension, quantity::DynDimension, Unit, UnitValue, Value};
+
+/// A value with an independent standard uncertainty, formatted as `9.81 ± 0.02`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub value: f64,
+    pub std_dev: f64,
+}
+
+impl Measurement {
+    pub fn new(value: f64, std_dev: f64) -> Self {
+        Self {
+            value,
+            std_dev: std_dev.abs(),
+        }
+    }
+
+    /// `std_dev / |value|`
+    pub fn relative(&self) -> f64 {
+        self.std_dev / self.value.abs()
+    }
+}
+
+impl From<f64> for Measurement {
+    fn from(value: f64) -> Self {
+        Self::new(value, 0.0)
+    }
+}
+
+impl UnitValue for Measurement {
+    fn apply_prefix(&self, power_of_ten: i32) -> Self {
+        let scale = 10f64.powi(power_of_ten);
+        Self::new(self.value * scale, self.std_dev * scale)
+    }
+
+    fn apply_factor(&self, factor: f64) -> Self {
+        Self::new(self.value * factor, self.std_dev * factor)
+    }
+}
+
+impl Add for Measurement {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.value + rhs.value, self.std_dev.hypot(rhs.std_dev))
+    }
+}
+
+impl Sub for Measurement {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.value - rhs.value, self.std_dev.hypot(rhs.std_dev))
+    }
+}
+
+impl Mul for Measurement {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.value * rhs.value,
+            (rhs.value * self.std_dev).hypot(self.value * rhs.std_dev),
+        )
+    }
+}
+
+impl Div for Measurement {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let value = self.value / rhs.value;
+        Self::new(
+            value,
+            (self.std_dev / rhs.value).hypot(value * rhs.std_dev / rhs.value),
+        )
+    }
+}
+
+impl Neg for Measurement {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.value, self.std_dev)
+    }
+}
+
+/// Exact factors, which only scale the uncertainty
+impl Mul<f64> for Measurement {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        self.apply_factor(rhs)
+    }
+}
+
+impl Mul<Measurement> for f64 {
+    type Output = Measurement;
+    fn mul(self, rhs: Measurement) -> Measurement {
+        rhs.apply_factor(self)
+    }
+}
+
+impl Div<f64> for Measurement {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        self.apply_factor(1.0 / rhs)
+    }
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        format_uncertain(self.value, self.std_dev, f)
+    }
+}
+
+/// A value which depends linearly on `N` independent inputs. `components[i]` is the
+/// contribution of the `i`th input to the standard uncertainty: its standard deviation times
+/// the partial derivative of the value by it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Correlated<const N: usize> {
+    pub value: f64,
+    pub components: [f64; N],
+}
+
+impl<const N: usize> Correlated<N> {
+    /// The `index`th independent input. Panics unless `index < N`.
+    pub fn input(index: usize, value: f64, std_dev: f64) -> Self {
+        assert!(index < N, "input {} of only {} inputs", index, N);
+        let mut components = [0.0; N];
+        components[index] = std_dev;
+        Self { value, components }
+    }
+
+    /// A value without uncertainty
+    pub fn exact(value: f64) -> Self {
+        Self {
+            value,
+            components: [0.0; N],
+        }
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.components.iter().map(|c| c * c).sum::<f64>().sqrt()
+    }
+
+    pub fn covariance(&self, other: &Self) -> f64 {
+        self.components
+            .iter()
+            .zip(other.components)
+            .map(|(a, b)| a * b)
+            .sum()
+    }
+
+    /// The correlation coefficient, between -1 and 1
+    pub fn correlation(&self, other: &Self) -> f64 {
+        self.covariance(other) / (self.std_dev() * other.std_dev())
+    }
+
+    /// Forgets the correlations
+    pub fn to_measurement(&self) -> Measurement {
+        Measurement::new(self.value, self.std_dev())
+    }
+
+    /// `value` with the components `a * self + b * rhs`
+    fn combine(self, value: f64, a: f64, rhs: Self, b: f64) -> Self {
+        let mut components = self.components;
+        for (c, r) in components.iter_mut().zip(rhs.components) {
+            *c = a * *c + b * r;
+        }
+        Self { value, components }
+    }
+}
+
+impl<const N: usize> UnitValue for Correlated<N> {
+    fn apply_prefix(&self, power_of_ten: i32) -> Self {
+        self.apply_factor(10f64.powi(power_of_ten))
+    }
+
+    fn apply_factor(&self, factor: f64) -> Self {
+        Self {
+            value: self.value * factor,
+            components: self.components.map(|c| c * factor),
+        }
+    }
+}
+
+impl<const N: usize> Add for Correlated<N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.combine(self.value + rhs.value, 1.0, rhs, 1.0)
+    }
+}
+
+impl<const N: usize> Sub for Correlated<N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.combine(self.value - rhs.value, 1.0, rhs, -1.0)
+    }
+}
+
+impl<const N: usize> Mul for Correlated<N> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.combine(self.value * rhs.value, rhs.value, rhs, self.value)
+    }
+}
+
+impl<const N: usize> Div for Correlated<N> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let value = self.value / rhs.value;
+        self.combine(value, 1.0 / rhs.value, rhs, -value / rhs.value)
+    }
+}
+
+impl<const N: usize> Neg for Correlated<N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.apply_factor(-1.0)
+    }
+}
+
+impl<const N: usize> Display for Correlated<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        format_uncertain(self.value, self.std_dev(), f)
+    }
+}
+
+/// Rounds the uncertainty to one significant digit (two if it starts with a 1) and the value to
+/// the same place, unless a precision is given
+fn format_uncertain(value: f64, std_dev: f64, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let decimals = match f.precision() {
+        Some(precision) => precision,
+        None if std_dev > 0.0 && std_dev.is_finite() => {
+            let exponent = std_dev.log10().floor() as i32;
+            let digits = if std_dev / 10f64.powi(exponent) < 2.0 {
+                2
+            } else {
+                1
+            };
+            (digits - 1 - exponent).max(0) as usize
+        }
+        None => return write!(f, "{} ± {}", value, std_dev),
+    };
+    write!(f, "{:.*} ± {:.*}", decimals, value, decimals, std_dev)
+}
+
+fn write_unit(dimension: &DynDimension, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match dimension.pretty() {
+        unit if unit.is_empty() => Ok(()),
+        unit => write!(f, " {}", unit),
+    }
+}
+
+/// Formats the value in the coherent SI unit of its dimension, e.g. `9.81 ± 0.02 m/s²`
+impl<P: Integer + ToInt<i32>, U: Unit, K> Display for Value<Measurement, P, U, K>
+where
+    U::Dimension: Dimension,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.to_base().value(), f)?;
+        write_unit(&DynDimension::of::<U::Dimension>(), f)
+    }
+}
+
+/// Formats the value in the coherent SI unit of its dimension
+impl<const N: usize, P: Integer + ToInt<i32>, U: Unit, K> Display for Value<Correlated<N>, P, U, K>
+where
+    U::Dimension: Dimension,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.to_base().value(), f)?;
+        write_unit(&DynDimension::of::<U::Dimension>(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Meter, Second},
+        prefix::{Kilo, Milli},
+    };
+    use typenum::Z0;
+
+    #[test]
+    fn propagation() {
+        let distance = Value::<_, Z0, Meter>::new(Measurement::new(19.62, 0.04));
+        let time = Value::<_, Z0, Second>::new(Measurement::new(1.0, 0.0));
+        let g = distance / (time * time);
+        assert_eq!(g.to_string(), "19.62 ± 0.04 m/s²");
+        assert_eq!(format!("{:.1}", g), "19.6 ± 0.0 m/s²");
+
+        let a = Kilo::<Meter>::new(Measurement::new(1.0, 0.003));
+        let b = Milli::<Meter>::new(Measurement::new(4000.0, 4.0));
+        let sum = a + b;
+        assert!((sum.value().value - 1_004_000.0).abs() < 1e-6);
+        assert!((sum.value().std_dev - 3000f64.hypot(4.0)).abs() < 1e-6);
+        assert_eq!(format!("{}", Measurement::new(9.81, 0.15)), "9.81 ± 0.15");
+
+        let area = a * a;
+        assert!((area.value().relative() - 0.003 * 2f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn correlated() {
+        let x = Value::<_, Z0, Meter>::new(Correlated::<2>::input(0, 2.0, 0.1));
+        let y = Value::<_, Z0, Meter>::new(Correlated::<2>::input(1, 3.0, 0.2));
+        assert_eq!((x - x).value().std_dev(), 0.0);
+        let sum = x + y;
+        assert!((sum.value().std_dev() - 0.1f64.hypot(0.2)).abs() < 1e-12);
+        assert!(sum.value().correlation(x.value()) > 0.0);
+        // d(x/x) = 0, but d(x*x) = 2x dx
+        assert_eq!((x / x).value().std_dev(), 0.0);
+        assert!(((x * x).value().std_dev() - 0.4).abs() < 1e-12);
+        assert_eq!(
+            Kilo::<Meter>::new(Correlated::<1>::input(0, 1.5, 0.25)).to_string(),
+            "1500 ± 250 m"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "input 2 of only 2 inputs")]
+    fn input_out_of_range() {
+        Correlated::<2>::input(2, 1.0, 0.1);
+    }
+}
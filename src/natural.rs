@@ -0,0 +1,201 @@
+//! Unit systems which set fundamental constants to one: natural units (`c = ħ = k_B = 1`, with
+//! everything measured in powers of the electronvolt), Planck units (`c = ħ = G = k_B = 1`) and
+//! Hartree atomic units (`ħ = m_e = e = 4π ε0 = 1`).
+//!
+//! These are `UnitSystem`s like any other, so `Value::to_system::<Hartree>()` converts into
+//! atomic units and `Value::convert` converts back. Amount of substance and luminous intensity
+//! keep their SI units.
+//!
+//! Setting constants to one only changes the numbers, not the dimensions: `Natural` prints a
+//! mass, an energy and a temperature all in `eV`, but they keep their SI dimension, so they
+//! can't be added or compared. `Value::to_energy` multiplies by `c^2` or `k_B` to turn a mass
+//! or a temperature into an actual energy.
+
+use crate::{
+    base::Coherent,
+    dimension::{Energy, Mass, Tempature},
+    gaussian::C,
+    quantity::DynDimension,
+    system::UnitSystem,
+    Unit, UnitValue, Value,
+};
+use typenum::{Integer, ToInt, Z0};
+
+/// The reduced Planck constant in `J s` (CODATA 2018)
+pub const HBAR: f64 = 1.054571817e-34;
+/// The gravitational constant in `m^3 kg^-1 s^-2` (CODATA 2018)
+pub const G: f64 = 6.67430e-11;
+/// The Boltzmann constant in `J/K`
+pub const K_B: f64 = 1.380649e-23;
+/// The elementary charge in `C`
+pub const E: f64 = 1.602176634e-19;
+/// The electron mass in `kg` (CODATA 2018)
+pub const M_E: f64 = 9.1093837015e-31;
+/// The Bohr radius in `m` (CODATA 2018)
+pub const A_0: f64 = 5.29177210903e-11;
+/// The Hartree energy in `J` (CODATA 2018)
+pub const E_H: f64 = 4.3597447222071e-18;
+
+/// Natural units with the electronvolt as the unit of energy: a mass is measured in `eV`
+/// (`eV/c^2`), a length in `eV^-1` (`ħc/eV`) and a temperature in `eV` (`eV/k_B`). Charge is
+/// Heaviside-Lorentz, so the elementary charge is `sqrt(4π α)`.
+///
+/// Only the printed symbol is reduced to a power of `eV`; see `Value::to_energy` for the
+/// conversion between a mass and an energy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Natural;
+
+impl UnitSystem for Natural {
+    const BASE_UNITS: [f64; 7] = [
+        HBAR * C / E,
+        HBAR / E,
+        E / (C * C),
+        1.0,
+        // sqrt(ε0 ħ c) eV / ħ
+        8.038167091384661e-4,
+        E / K_B,
+        1.0,
+    ];
+    const SYMBOLS: [&'static str; 7] = ["eV^-1", "eV^-1", "eV", "mol", "eV", "eV", "cd"];
+
+    /// Every dimension but amount and luminous intensity is a power of `eV`, e.g. `eV^2` for a
+    /// force
+    fn symbol(dimension: &DynDimension) -> String {
+        let exponents = dimension.exponents();
+        let [l, t, m, n, i, th, j] = exponents.map(|(num, den)| num as f64 / den as f64);
+        let energy = m + i + th - l - t;
+        let parts: Vec<String> = [("eV", energy), ("mol", n), ("cd", j)]
+            .iter()
+            .filter(|(_, exp)| *exp != 0.0)
+            .map(|(symbol, exp)| {
+                if *exp == 1.0 {
+                    symbol.to_string()
+                } else {
+                    format!("{}^{}", symbol, exp)
+                }
+            })
+            .collect();
+        if parts.is_empty() {
+            "1".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+/// A dimension that `c = k_B = 1` identifies with energy
+pub trait EnergyEquivalent {
+    /// The energy in `J` of one coherent SI unit of this dimension
+    const ENERGY: f64;
+}
+
+impl EnergyEquivalent for Energy {
+    const ENERGY: f64 = 1.0;
+}
+
+impl EnergyEquivalent for Mass {
+    const ENERGY: f64 = C * C;
+}
+
+impl EnergyEquivalent for Tempature {
+    const ENERGY: f64 = K_B;
+}
+
+impl<V: UnitValue, P: Integer + ToInt<i32>, U: Unit, K> Value<V, P, U, K>
+where
+    U::Dimension: EnergyEquivalent,
+{
+    /// Converts a mass (`E = m c^2`) or a temperature (`E = k_B T`) into joules
+    pub fn to_energy(&self) -> Value<V, Z0, Coherent<Energy>, K> {
+        Value::new(self.to_base().value().rescale(0, U::Dimension::ENERGY))
+    }
+}
+
+/// Planck units: the Planck length, time, mass and temperature, and the Planck charge
+/// `sqrt(4π ε0 ħ c)` per Planck time for current
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Planck;
+
+impl UnitSystem for Planck {
+    // sqrt(ħ G / c^3), l_P / c, sqrt(ħ c / G), q_P / t_P and m_P c^2 / k_B
+    const BASE_UNITS: [f64; 7] = [
+        1.61625502392855e-35,
+        5.391246446661944e-44,
+        2.176434342051127e-8,
+        1.0,
+        3.478872753751307e25,
+        1.4167841617232972e32,
+        1.0,
+    ];
+    const SYMBOLS: [&'static str; 7] = ["l_P", "t_P", "m_P", "mol", "I_P", "T_P", "cd"];
+}
+
+/// Hartree atomic units: the Bohr radius, the electron mass, the elementary charge and the
+/// Hartree energy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hartree;
+
+impl UnitSystem for Hartree {
+    const BASE_UNITS: [f64; 7] = [A_0, HBAR / E_H, M_E, 1.0, E * E_H / HBAR, E_H / K_B, 1.0];
+    const SYMBOLS: [&'static str; 7] = ["a0", "ħ/E_h", "m_e", "mol", "e E_h/ħ", "E_h/k_B", "cd"];
+    const NAMED: &'static [(DynDimension, &'static str)] =
+        &[(DynDimension::from_ints([2, -2, 1, 0, 0, 0, 0]), "E_h")];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::Coherent,
+        close,
+        dimension::Speed,
+        quantity::Quantity,
+        system::SystemUnit,
+    };
+
+    // The CODATA values of a0, m_e and E_h only agree with each other to about 1e-9
+    #[test]
+    fn constants_are_one() {
+        let hbar: Quantity = "1.054571817e-34 J s".parse().unwrap();
+        let c: Quantity = "299792458 m/s".parse().unwrap();
+        let g: Quantity = "6.67430e-11 m^3 / kg s^2".parse().unwrap();
+        for (value, natural, planck, hartree) in [
+            (hbar, 1.0, 1.0, 1.0),
+            (c, 1.0, 1.0, 137.035999084),
+            (g, 6.7088e-57, 1.0, 2.4006e-43),
+        ] {
+            assert!((value.in_system::<Natural>() - natural).abs() <= 1e-4 * natural);
+            assert!((value.in_system::<Planck>() - planck).abs() <= 1e-4 * planck);
+            assert!((value.in_system::<Hartree>() - hartree).abs() <= 1e-4 * hartree);
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        let electron = Value::<f64, Z0, Coherent<Mass>>::new(M_E);
+        let natural = electron.to_system::<Natural>();
+        assert!(close(*natural.value(), 510_998.95, 1e-8));
+        assert!(format!("{:?}", natural).ends_with(" eV"));
+        assert!(close(electron.in_system::<Hartree>(), 1.0, 1e-8));
+
+        assert!(close(*electron.to_energy().value(), M_E * C * C, 1e-12));
+        let electron_ev = electron.to_energy().to_system::<Natural>();
+        assert!(close(*electron_ev.value(), *natural.value(), 1e-12));
+        let room = Value::<f64, Z0, Coherent<Tempature>>::new(300.0);
+        assert!(close(room.to_energy().in_system::<Natural>(), 0.025852, 1e-4));
+
+        let hartree = Value::<f64, Z0, SystemUnit<Hartree, Energy>>::new(1.0);
+        assert!(format!("{:?}", hartree).ends_with(" E_h"));
+        assert!(close(hartree.in_system::<Natural>(), 27.211386245988, 1e-8));
+        let joules: Value<f64, Z0, Coherent<Energy>> = hartree.convert();
+        assert!(close(*joules.value(), E_H, 1e-8));
+
+        let speed = Value::<f64, Z0, Coherent<Speed>>::new(C / 2.0);
+        assert!(close(speed.in_system::<Planck>(), 0.5, 1e-8));
+        assert_eq!(Natural::symbol(&DynDimension::of::<Speed>()), "1");
+        assert_eq!(
+            Natural::symbol(&DynDimension::from_ints([1, 0, 0, 0, 0, 0, 0])),
+            "eV^-1"
+        );
+    }
+}
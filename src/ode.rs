@@ -0,0 +1,367 @@
+//! Ordinary differential equations over unit-typed state.
+//!
+//! The state is a `Value` or a tuple of them, and its derivative by the independent variable
+//! (usually time) is checked against it: the right hand side of `dx/dt = f(t, x)` for a length
+//! `x` must return a speed, for a `(position, velocity)` state a `(velocity, acceleration)`.
+//!
+//! ```compile_fail
+//! # use units::{base::{Coherent, Meter, Second}, dimension::Acceleration, ode::rk4, Value};
+//! # use typenum::Z0;
+//! // The rate of change of a length can't be an acceleration
+//! let f = |_: &Value<f64, Z0, Second>, _: &Value<f64, Z0, Meter>| {
+//!     Value::<f64, Z0, Coherent<Acceleration>>::new(1.0)
+//! };
+//! rk4(f, Value::new(0.0), Value::new(0.0), Value::new(0.1), 10);
+//! ```
+
+use std::{
+    fmt::Display,
+    ops::{Div, Mul},
+};
+
+use typenum::{Integer, ToInt};
+
+use crate::{Unit, Value};
+
+/// The state of an ODE with independent variable `T`
+pub trait State<T>: Clone {
+    /// The derivative of the state by `T`, e.g. a speed for a length over time
+    type Rate;
+
+    /// `self + factor * rate * dt`
+    fn advance(&self, rate: &Self::Rate, dt: &T, factor: f64) -> Self;
+
+    /// The largest ratio of `|self - other|` to `tolerance` over the components
+    fn error_ratio(&self, other: &Self, tolerance: &Self) -> f64;
+}
+
+impl<P, U, K, TP, TU, TK, RP, RU, RK, IP, IU> State<Value<f64, TP, TU, TK>> for Value<f64, P, U, K>
+where
+    P: Integer + ToInt<i32> + Clone,
+    U: Unit + Clone,
+    K: Clone,
+    TP: Integer,
+    TU: Unit,
+    RP: Integer,
+    RU: Unit,
+    IP: Integer + ToInt<i32>,
+    IU: Unit<Dimension = U::Dimension>,
+    Self: Div<Value<f64, TP, TU, TK>, Output = Value<f64, RP, RU, RK>>,
+    Value<f64, RP, RU, RK>: Mul<Value<f64, TP, TU, TK>, Output = Value<f64, IP, IU, K>>,
+{
+    type Rate = Value<f64, RP, RU, RK>;
+
+    fn advance(&self, rate: &Self::Rate, dt: &Value<f64, TP, TU, TK>, factor: f64) -> Self {
+        let increment = Value::<f64, RP, RU, RK>::new(rate.0) * Value::new(dt.0);
+        Value::new(self.0 + factor * increment.convert::<P, U>().0)
+    }
+
+    fn error_ratio(&self, other: &Self, tolerance: &Self) -> f64 {
+        (self.0 - other.0).abs() / tolerance.0.abs()
+    }
+}
+
+/// The maximum, or NaN if any ratio is NaN
+fn max_ratio(ratios: &[f64]) -> f64 {
+    ratios
+        .iter()
+        .fold(0.0, |max, &r| if r > max || r.is_nan() { r } else { max })
+}
+
+macro_rules! tuple_state {
+    ($($name:ident $index:tt),*) => {
+        impl<T, $($name: State<T>),*> State<T> for ($($name,)*) {
+            type Rate = ($($name::Rate,)*);
+
+            fn advance(&self, rate: &Self::Rate, dt: &T, factor: f64) -> Self {
+                ($(self.$index.advance(&rate.$index, dt, factor),)*)
+            }
+
+            fn error_ratio(&self, other: &Self, tolerance: &Self) -> f64 {
+                max_ratio(&[$(self.$index.error_ratio(&other.$index, &tolerance.$index)),*])
+            }
+        }
+    };
+}
+tuple_state!(A 0, B 1);
+tuple_state!(A 0, B 1, C 2);
+tuple_state!(A 0, B 1, C 2, D 3);
+
+/// The points `(t, y)` of a solution, in order
+pub type Trajectory<S, P, U, K> = Vec<(Value<f64, P, U, K>, S)>;
+
+/// `t + factor * h`
+fn offset<P: Integer, U: Unit, K>(
+    t: &Value<f64, P, U, K>,
+    h: &Value<f64, P, U, K>,
+    factor: f64,
+) -> Value<f64, P, U, K> {
+    Value::new(t.0 + factor * h.0)
+}
+
+/// One classic fourth order Runge-Kutta step of size `h` from `y` at `t`
+pub fn rk4_step<S, P, U, K>(
+    f: &impl Fn(&Value<f64, P, U, K>, &S) -> S::Rate,
+    t: &Value<f64, P, U, K>,
+    y: &S,
+    h: &Value<f64, P, U, K>,
+) -> S
+where
+    S: State<Value<f64, P, U, K>>,
+    P: Integer,
+    U: Unit,
+{
+    let middle = offset(t, h, 0.5);
+    let k1 = f(t, y);
+    let k2 = f(&middle, &y.advance(&k1, h, 0.5));
+    let k3 = f(&middle, &y.advance(&k2, h, 0.5));
+    let k4 = f(&offset(t, h, 1.0), &y.advance(&k3, h, 1.0));
+    y.advance(&k1, h, 1.0 / 6.0)
+        .advance(&k2, h, 1.0 / 3.0)
+        .advance(&k3, h, 1.0 / 3.0)
+        .advance(&k4, h, 1.0 / 6.0)
+}
+
+/// `steps` fourth order Runge-Kutta steps of size `h`, returning every point including the
+/// initial one
+pub fn rk4<S, P, U, K>(
+    f: impl Fn(&Value<f64, P, U, K>, &S) -> S::Rate,
+    t0: Value<f64, P, U, K>,
+    y0: S,
+    h: Value<f64, P, U, K>,
+    steps: usize,
+) -> Trajectory<S, P, U, K>
+where
+    S: State<Value<f64, P, U, K>>,
+    P: Integer,
+    U: Unit,
+{
+    let mut points = Vec::with_capacity(steps + 1);
+    let (mut t, mut y) = (t0, y0);
+    for i in 1..=steps {
+        let next = rk4_step(&f, &t, &y, &h);
+        points.push((t, y));
+        t = offset(&points[0].0, &h, i as f64);
+        y = next;
+    }
+    points.push((t, y));
+    points
+}
+
+/// The Dormand-Prince 5(4) tableau: the nodes, the coupling coefficients (the last row is the
+/// fifth order solution) and the weights of the embedded fourth order solution
+const C: [f64; 7] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+const A: [[f64; 6]; 7] = [
+    [0.0; 6],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+const B4: [f64; 7] = [
+    5179.0 / 57600.0,
+    0.0,
+    7571.0 / 16695.0,
+    393.0 / 640.0,
+    -92097.0 / 339200.0,
+    187.0 / 2100.0,
+    1.0 / 40.0,
+];
+
+/// `y + h * sum(weights[i] * rates[i])`
+fn combine<S: State<T>, T>(y: &S, rates: &[S::Rate], weights: &[f64], h: &T) -> S {
+    rates
+        .iter()
+        .zip(weights)
+        .filter(|(_, &weight)| weight != 0.0)
+        .fold(y.clone(), |y, (rate, &weight)| y.advance(rate, h, weight))
+}
+
+/// One Dormand-Prince step, returning the fifth order solution and its error ratio
+fn rk45_step<S, P, U, K>(
+    f: &impl Fn(&Value<f64, P, U, K>, &S) -> S::Rate,
+    t: &Value<f64, P, U, K>,
+    y: &S,
+    h: &Value<f64, P, U, K>,
+    tolerance: &S,
+) -> (S, f64)
+where
+    S: State<Value<f64, P, U, K>>,
+    P: Integer,
+    U: Unit,
+{
+    let mut rates = Vec::with_capacity(7);
+    rates.push(f(t, y));
+    let mut fifth = y.clone();
+    for (c, a) in C.iter().zip(&A).skip(1) {
+        fifth = combine(y, &rates, a, h);
+        rates.push(f(&offset(t, h, *c), &fifth));
+    }
+    let fourth = combine(y, &rates, &B4, h);
+    let error = fifth.error_ratio(&fourth, tolerance);
+    (fifth, error)
+}
+
+/// Why `rk45` gave up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OdeError {
+    /// The step size needed for the tolerance became negligible at this value of the
+    /// independent variable, in its unit
+    StepSizeUnderflow(f64),
+}
+
+impl Display for OdeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OdeError::StepSizeUnderflow(t) => write!(f, "step size underflow at {}", t),
+        }
+    }
+}
+
+impl std::error::Error for OdeError {}
+
+/// Integrates from `t0` to `t_end` with the adaptive Dormand-Prince 5(4) method, keeping the
+/// local error of every component within the absolute `tolerance` (in the units of the state).
+/// Returns every accepted point including the initial one.
+pub fn rk45<S, P, U, K>(
+    f: impl Fn(&Value<f64, P, U, K>, &S) -> S::Rate,
+    t0: Value<f64, P, U, K>,
+    y0: S,
+    t_end: Value<f64, P, U, K>,
+    tolerance: &S,
+) -> Result<Trajectory<S, P, U, K>, OdeError>
+where
+    S: State<Value<f64, P, U, K>>,
+    P: Integer,
+    U: Unit,
+{
+    let span = t_end.0 - t0.0;
+    let mut h = span / 100.0;
+    let mut points = vec![(t0, y0)];
+    loop {
+        let (t, y) = points.last().unwrap();
+        let remaining = t_end.0 - t.0;
+        if remaining * span.signum() <= 0.0 {
+            return Ok(points);
+        }
+        if h.abs() > remaining.abs() {
+            h = remaining;
+        }
+        let (next, error) = rk45_step(&f, t, y, &Value::new(h), tolerance);
+        let accepted = error <= 1.0;
+        if accepted {
+            let t = Value::new(t.0 + h);
+            points.push((t, next));
+        }
+        h *= if error.is_finite() {
+            (0.9 * error.powf(-0.2)).clamp(0.2, 5.0)
+        } else {
+            0.2
+        };
+        let t = points.last().unwrap().0 .0;
+        if !accepted && h.abs() <= 1e-12 * t.abs().max(span.abs()) {
+            return Err(OdeError::StepSizeUnderflow(t));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Coherent, Meter, Second},
+        dimension::{Acceleration, Frequency, Speed},
+    };
+    use typenum::Z0;
+
+    type Seconds = Value<f64, Z0, Second>;
+    type Meters = Value<f64, Z0, Meter>;
+    type Speeds = Value<f64, Z0, Coherent<Speed>>;
+
+    /// A mass on a spring, starting 1 m from rest: x = cos(ωt)
+    fn oscillator(
+        _: &Seconds,
+        (x, v): &(Meters, Speeds),
+    ) -> (Speeds, Value<f64, Z0, Coherent<Acceleration>>) {
+        let omega = Value::<f64, Z0, Coherent<Frequency>>::new(2.0);
+        (*v, -(omega * omega * *x))
+    }
+
+    #[test]
+    fn fixed_steps() {
+        let start = (Meters::new(1.0), Speeds::new(0.0));
+        let points = rk4(
+            oscillator,
+            Seconds::new(0.0),
+            start,
+            Seconds::new(0.01),
+            300,
+        );
+        assert_eq!(points.len(), 301);
+        let (t, (x, v)) = points[300];
+        assert!((t.value() - 3.0).abs() < 1e-12);
+        assert!((x.value() - 6f64.cos()).abs() < 1e-6);
+        assert!((v.value() + 2.0 * 6f64.sin()).abs() < 1e-6);
+
+        // dx/dt = 3 m/s
+        let line = rk4(
+            |_, _: &Meters| Speeds::new(3.0),
+            Seconds::new(0.0),
+            Meters::new(1.0),
+            Seconds::new(0.5),
+            4,
+        );
+        assert_eq!(*line[4].1.value(), 7.0);
+    }
+
+    #[test]
+    fn adaptive() {
+        let start = (Meters::new(1.0), Speeds::new(0.0));
+        let tolerance = (Meters::new(1e-9), Speeds::new(1e-9));
+        let points = rk45(
+            oscillator,
+            Seconds::new(0.0),
+            start,
+            Seconds::new(10.0),
+            &tolerance,
+        )
+        .unwrap();
+        let (t, (x, _)) = *points.last().unwrap();
+        assert_eq!(*t.value(), 10.0);
+        assert!((x.value() - 20f64.cos()).abs() < 1e-6);
+        assert!(points.len() < 1000);
+
+        // x' = x^2 blows up at t = 1
+        let blow_up = rk45(
+            |_, x: &Meters| Value::<f64, Z0, Coherent<Speed>>::new(x.value() * x.value()),
+            Seconds::new(0.0),
+            Meters::new(1.0),
+            Seconds::new(2.0),
+            &Meters::new(1e-6),
+        );
+        assert!(matches!(blow_up, Err(OdeError::StepSizeUnderflow(t)) if (t - 1.0).abs() < 1e-2));
+    }
+}
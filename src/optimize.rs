@@ -0,0 +1,378 @@
+//! Root finding and minimization of functions of one value.
+//!
+//! The argument of the function, the bracket and the tolerance all carry units: the tolerance
+//! may use any unit of the dimension of the argument, e.g. a diameter in meters solved to a
+//! tenth of a millimeter. The solvers work on the numbers in the unit of the bracket.
+
+use std::fmt::Display;
+
+use typenum::{Integer, ToInt};
+
+use crate::{Unit, Value};
+
+const MAX_ITERATIONS: usize = 200;
+/// `(3 - sqrt(5)) / 2`, the golden section of an interval
+const GOLDEN: f64 = 0.381_966_011_250_105_1;
+
+/// The argument of a minimum and the value of the function there
+pub type Minimum<XP, XU, XK, YP, YU, YK> = (Value<f64, XP, XU, XK>, Value<f64, YP, YU, YK>);
+
+/// Why a solver gave up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// The function has the same sign at both ends of the bracket
+    NotBracketed,
+    /// The tolerance wasn't reached within the iteration limit
+    NoConvergence,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::NotBracketed => write!(f, "the bracket doesn't contain a sign change"),
+            SolveError::NoConvergence => write!(f, "no convergence"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// `f` on the numbers in the units of the argument and result
+fn raw<XP: Integer, XU: Unit, XK, Y>(
+    f: impl Fn(Value<f64, XP, XU, XK>) -> Y,
+    result: impl Fn(Y) -> f64,
+) -> impl Fn(f64) -> f64 {
+    move |x| result(f(Value::new(x)))
+}
+
+/// The tolerance in the units of the argument
+fn tolerance_in<XP, XU, TP, TU, TK>(tolerance: Value<f64, TP, TU, TK>) -> f64
+where
+    XP: Integer + ToInt<i32>,
+    XU: Unit<Dimension = TU::Dimension>,
+    TP: Integer + ToInt<i32>,
+    TU: Unit,
+{
+    tolerance.convert::<XP, XU>().0.abs()
+}
+
+/// A root of `f` between `a` and `b`, to within `tolerance`, by bisection. `f` must change sign
+/// between `a` and `b`.
+pub fn bisection<XP, XU, XK, YP, YU, YK, TP, TU, TK>(
+    f: impl Fn(Value<f64, XP, XU, XK>) -> Value<f64, YP, YU, YK>,
+    a: Value<f64, XP, XU, XK>,
+    b: Value<f64, XP, XU, XK>,
+    tolerance: Value<f64, TP, TU, TK>,
+) -> Result<Value<f64, XP, XU, XK>, SolveError>
+where
+    XP: Integer + ToInt<i32>,
+    XU: Unit<Dimension = TU::Dimension>,
+    YP: Integer,
+    YU: Unit,
+    TP: Integer + ToInt<i32>,
+    TU: Unit,
+{
+    let f = raw(f, |y| y.0);
+    let tolerance = tolerance_in::<XP, XU, _, _, _>(tolerance);
+    let (mut a, mut b) = (a.0, b.0);
+    let (fa, fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Ok(Value::new(a));
+    } else if fb == 0.0 {
+        return Ok(Value::new(b));
+    } else if fa.signum() == fb.signum() {
+        return Err(SolveError::NotBracketed);
+    }
+    for _ in 0..MAX_ITERATIONS {
+        let middle = 0.5 * (a + b);
+        if (b - a).abs() <= 2.0 * tolerance {
+            return Ok(Value::new(middle));
+        }
+        let fm = f(middle);
+        if fm == 0.0 {
+            return Ok(Value::new(middle));
+        } else if fm.signum() == fa.signum() {
+            a = middle;
+        } else {
+            b = middle;
+        }
+    }
+    Err(SolveError::NoConvergence)
+}
+
+/// A root of `f` between `a` and `b`, to within `tolerance`, by Brent's method: inverse
+/// quadratic interpolation, falling back to bisection. `f` must change sign between `a` and `b`.
+pub fn brent<XP, XU, XK, YP, YU, YK, TP, TU, TK>(
+    f: impl Fn(Value<f64, XP, XU, XK>) -> Value<f64, YP, YU, YK>,
+    a: Value<f64, XP, XU, XK>,
+    b: Value<f64, XP, XU, XK>,
+    tolerance: Value<f64, TP, TU, TK>,
+) -> Result<Value<f64, XP, XU, XK>, SolveError>
+where
+    XP: Integer + ToInt<i32>,
+    XU: Unit<Dimension = TU::Dimension>,
+    YP: Integer,
+    YU: Unit,
+    TP: Integer + ToInt<i32>,
+    TU: Unit,
+{
+    let f = raw(f, |y| y.0);
+    let tolerance = tolerance_in::<XP, XU, _, _, _>(tolerance);
+    let (mut a, mut b) = (a.0, b.0);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Ok(Value::new(a));
+    } else if fb == 0.0 {
+        return Ok(Value::new(b));
+    } else if fa.signum() == fb.signum() {
+        return Err(SolveError::NotBracketed);
+    }
+    // `b` is the best estimate, `c` the other end of the bracket, `a` the previous estimate
+    let (mut c, mut fc) = (a, fa);
+    let (mut d, mut e) = (b - a, b - a);
+    for _ in 0..MAX_ITERATIONS {
+        if fb.signum() == fc.signum() {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+        let tol = 2.0 * f64::EPSILON * b.abs() + 0.5 * tolerance;
+        let half = 0.5 * (c - b);
+        if half.abs() <= tol || fb == 0.0 {
+            return Ok(Value::new(b));
+        }
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // Secant
+                (2.0 * half * s, 1.0 - s)
+            } else {
+                // Inverse quadratic interpolation
+                let (q, r) = (fa / fc, fb / fc);
+                (
+                    s * (2.0 * half * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            if 2.0 * p < (3.0 * half * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = half;
+                e = d;
+            }
+        } else {
+            d = half;
+            e = d;
+        }
+        (a, fa) = (b, fb);
+        b += if d.abs() > tol { d } else { tol.copysign(half) };
+        fb = f(b);
+    }
+    Err(SolveError::NoConvergence)
+}
+
+/// A local minimum of `f` between `a` and `b`, to within `tolerance`, by golden section search.
+/// Returns the argument and the value there.
+pub fn golden_section<XP, XU, XK, YP, YU, YK, TP, TU, TK>(
+    f: impl Fn(Value<f64, XP, XU, XK>) -> Value<f64, YP, YU, YK>,
+    a: Value<f64, XP, XU, XK>,
+    b: Value<f64, XP, XU, XK>,
+    tolerance: Value<f64, TP, TU, TK>,
+) -> Result<Minimum<XP, XU, XK, YP, YU, YK>, SolveError>
+where
+    XP: Integer + ToInt<i32>,
+    XU: Unit<Dimension = TU::Dimension>,
+    YP: Integer,
+    YU: Unit,
+    TP: Integer + ToInt<i32>,
+    TU: Unit,
+{
+    let g = raw(&f, |y| y.0);
+    let tolerance = tolerance_in::<XP, XU, _, _, _>(tolerance);
+    let (mut a, mut b) = (a.0.min(b.0), a.0.max(b.0));
+    let (mut x1, mut x2) = (a + GOLDEN * (b - a), b - GOLDEN * (b - a));
+    let (mut f1, mut f2) = (g(x1), g(x2));
+    for _ in 0..MAX_ITERATIONS {
+        if b - a <= 2.0 * tolerance {
+            let x = 0.5 * (a + b);
+            return Ok((Value::new(x), f(Value::new(x))));
+        }
+        if f1 < f2 {
+            b = x2;
+            (x2, f2) = (x1, f1);
+            x1 = a + GOLDEN * (b - a);
+            f1 = g(x1);
+        } else {
+            a = x1;
+            (x1, f1) = (x2, f2);
+            x2 = b - GOLDEN * (b - a);
+            f2 = g(x2);
+        }
+    }
+    Err(SolveError::NoConvergence)
+}
+
+/// A local minimum of `f` between `a` and `b`, to within `tolerance`, by Brent's method:
+/// parabolic interpolation, falling back to golden section steps. Returns the argument and the
+/// value there.
+pub fn brent_minimum<XP, XU, XK, YP, YU, YK, TP, TU, TK>(
+    f: impl Fn(Value<f64, XP, XU, XK>) -> Value<f64, YP, YU, YK>,
+    a: Value<f64, XP, XU, XK>,
+    b: Value<f64, XP, XU, XK>,
+    tolerance: Value<f64, TP, TU, TK>,
+) -> Result<Minimum<XP, XU, XK, YP, YU, YK>, SolveError>
+where
+    XP: Integer + ToInt<i32>,
+    XU: Unit<Dimension = TU::Dimension>,
+    YP: Integer,
+    YU: Unit,
+    TP: Integer + ToInt<i32>,
+    TU: Unit,
+{
+    let g = raw(&f, |y| y.0);
+    let tolerance = tolerance_in::<XP, XU, _, _, _>(tolerance);
+    let (mut a, mut b) = (a.0.min(b.0), a.0.max(b.0));
+    // `x` is the best point so far, `w` the second best and `v` the previous `w`
+    let mut x = a + GOLDEN * (b - a);
+    let (mut w, mut v) = (x, x);
+    let mut fx = g(x);
+    let (mut fw, mut fv) = (fx, fx);
+    // The last step and the one before
+    let (mut d, mut e) = (0.0f64, 0.0f64);
+    for _ in 0..MAX_ITERATIONS {
+        let middle = 0.5 * (a + b);
+        let tol = f64::EPSILON.sqrt() * x.abs() + tolerance / 3.0;
+        if (x - middle).abs() <= 2.0 * tol - 0.5 * (b - a) {
+            return Ok((Value::new(x), f(Value::new(x))));
+        }
+        let mut golden = true;
+        if e.abs() > tol {
+            // Fit a parabola through x, w and v
+            let r = (x - w) * (fx - fv);
+            let q = (x - v) * (fx - fw);
+            let mut p = (x - v) * q - (x - w) * r;
+            let mut q = 2.0 * (q - r);
+            if q > 0.0 {
+                p = -p;
+            }
+            q = q.abs();
+            if p.abs() < (0.5 * q * e).abs() && p > q * (a - x) && p < q * (b - x) {
+                e = d;
+                d = p / q;
+                if (x + d) - a < 2.0 * tol || b - (x + d) < 2.0 * tol {
+                    d = tol.copysign(middle - x);
+                }
+                golden = false;
+            }
+        }
+        if golden {
+            e = if x >= middle { a - x } else { b - x };
+            d = GOLDEN * e;
+        }
+        let u = if d.abs() >= tol {
+            x + d
+        } else {
+            x + tol.copysign(d)
+        };
+        let fu = g(u);
+        if fu <= fx {
+            if u >= x {
+                a = x;
+            } else {
+                b = x;
+            }
+            (v, fv) = (w, fw);
+            (w, fw) = (x, fx);
+            (x, fx) = (u, fu);
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                (v, fv) = (w, fw);
+                (w, fw) = (u, fu);
+            } else if fu <= fv || v == x || v == w {
+                (v, fv) = (u, fu);
+            }
+        }
+    }
+    Err(SolveError::NoConvergence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Coherent, Meter, Second},
+        dimension::{Pressure, Volume},
+        prefix::{Micro, Milli},
+    };
+    use typenum::{P3, Z0};
+
+    type Meters = Value<f64, Z0, Meter>;
+
+    #[test]
+    fn roots() {
+        // The side of a cube of 2 m^3
+        let volume =
+            |side: Meters| side * side * side - Value::<f64, Z0, Coherent<Volume>>::new(2.0);
+        let exact = 2f64.cbrt();
+        let tolerance = Micro::<Meter>::new(1.0);
+        for solver in [bisection, brent] {
+            let side = solver(volume, Meters::new(0.0), Meters::new(5.0), tolerance).unwrap();
+            assert!((side.value() - exact).abs() <= 1e-6);
+        }
+        assert_eq!(
+            brent(volume, Meters::new(2.0), Meters::new(5.0), tolerance),
+            Err(SolveError::NotBracketed)
+        );
+
+        // The pipe diameter for a pressure drop of 1 kPa, by Darcy-Weisbach with a friction
+        // factor of 0.02 over 10 m of pipe carrying 1 l/s of water
+        let pressure_drop = |diameter: Value<f64, P3, Meter>| {
+            let d = *diameter.to_base().value();
+            let speed = 1e-3 / (std::f64::consts::PI * d * d / 4.0);
+            Value::<f64, Z0, Coherent<Pressure>>::new(
+                0.02 * 10.0 * 1000.0 * speed * speed / (2.0 * d) - 1000.0,
+            )
+        };
+        let diameter = brent(
+            pressure_drop,
+            Milli::<Meter>::new(10.0),
+            Milli::<Meter>::new(200.0),
+            Milli::<Meter>::new(0.01),
+        )
+        .unwrap();
+        assert!(pressure_drop(diameter).value().abs() < 1.0);
+        assert!((diameter.value() - 43.85).abs() < 0.01);
+    }
+
+    #[test]
+    fn minima() {
+        // A ball thrown up at 9.81 m/s is highest after 1 s, where its depth below the
+        // thrower's hand is lowest
+        let depth = |t: Value<f64, Z0, Second>| {
+            let t = *t.value();
+            Meters::new(-(9.81 * t - 9.81 / 2.0 * t * t))
+        };
+        let tolerance = Milli::<Second>::new(0.001);
+        for minimizer in [golden_section, brent_minimum] {
+            let (t, lowest) =
+                minimizer(depth, Value::new(0.0), Value::new(3.0), tolerance).unwrap();
+            assert!((t.value() - 1.0).abs() < 1e-5);
+            assert!((lowest.value() + 4.905).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    quantity::{DimensionError, DynDimension, Quantity},
+    registry,
+};
+
+/// What went wrong while parsing a unit expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    UnexpectedEnd,
+    UnexpectedChar(char),
+    UnknownUnit(String),
+    InvalidNumber,
+    /// An exponent of the dimension doesn't fit in an `i32`, e.g. in `(m^65536)^65536`
+    ExponentOutOfRange,
+    /// The sides of a `+` or `-` have different dimensions
+    Dimension(Box<DimensionError>),
+}
+
+/// A parse error and the byte offset into the input where it happened
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub position: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseErrorKind::UnexpectedChar(c) => {
+                write!(f, "unexpected '{}' at position {}", c, self.position)
+            }
+            ParseErrorKind::UnknownUnit(unit) => write!(f, "unknown unit '{}'", unit),
+            ParseErrorKind::InvalidNumber => {
+                write!(f, "invalid number at position {}", self.position)
+            }
+            ParseErrorKind::ExponentOutOfRange => {
+                write!(f, "exponent out of range at position {}", self.position)
+            }
+            ParseErrorKind::Dimension(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses expressions like `60 mi/h`, `3 kN*m`, `9.81 m s^-2` or `2 cm3 + 1 mL`.
+///
+/// From loosest to tightest binding: `+` and `-`, then `*` and `/`, then juxtaposition
+/// (so `J/kg K` is `J/(kg*K)`), then `^` with an integer exponent, then `|` which divides two
+/// numbers. Digits directly after a unit name are an exponent too, `cm3` is `cm^3`.
+impl FromStr for Quantity {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        registry::global().parse(s)
+    }
+}
+
+/// Parses an expression, resolving every unit name with `resolve` instead of the global registry
+pub fn parse_with(
+    input: &str,
+    resolve: impl Fn(&str) -> Option<Quantity>,
+) -> Result<Quantity, ParseError> {
+    let mut parser = Parser {
+        input,
+        position: 0,
+        resolve: &resolve,
+    };
+    let ret = parser.sum()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(ret),
+        Some(c) => Err(parser.error(ParseErrorKind::UnexpectedChar(c))),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    resolve: &'a dyn Fn(&str) -> Option<Quantity>,
+}
+
+impl Parser<'_> {
+    fn sum(&mut self) -> Result<Quantity, ParseError> {
+        let mut ret = self.product()?;
+        loop {
+            self.skip_whitespace();
+            let op = self.position;
+            let result = match self.peek() {
+                Some('+') => {
+                    self.bump();
+                    ret.try_add(self.product()?)
+                }
+                Some('-') => {
+                    self.bump();
+                    ret.try_sub(self.product()?)
+                }
+                _ => return Ok(ret),
+            };
+            ret = result.map_err(|err| ParseError {
+                position: op,
+                kind: ParseErrorKind::Dimension(Box::new(err)),
+            })?;
+        }
+    }
+
+    fn product(&mut self) -> Result<Quantity, ParseError> {
+        let mut ret = self.juxtaposition()?;
+        loop {
+            self.skip_whitespace();
+            let op = self.position;
+            let result = match self.peek() {
+                Some('*') => {
+                    self.bump();
+                    ret.checked_mul(self.juxtaposition()?)
+                }
+                Some('/') => {
+                    self.bump();
+                    ret.checked_div(self.juxtaposition()?)
+                }
+                _ => return Ok(ret),
+            };
+            ret = self.in_range(result, op)?;
+        }
+    }
+
+    fn juxtaposition(&mut self) -> Result<Quantity, ParseError> {
+        let mut ret = self.power()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(c) if c.is_ascii_digit() || c == '.' || c == '(' || is_unit_char(c) => {
+                    let start = self.position;
+                    let result = ret.checked_mul(self.power()?);
+                    ret = self.in_range(result, start)?;
+                }
+                _ => return Ok(ret),
+            }
+        }
+    }
+
+    fn power(&mut self) -> Result<Quantity, ParseError> {
+        self.skip_whitespace();
+        // A leading minus binds looser than `^`, `-x^2` is `-(x^2)`
+        if self.peek() == Some('-') {
+            self.bump();
+            return Ok(self.power()? * -1.0);
+        }
+        let base = self.primary()?;
+        self.skip_whitespace();
+        if self.peek() == Some('^') {
+            let op = self.position;
+            self.bump();
+            self.skip_whitespace();
+            let result = base.checked_powi(self.integer()?);
+            self.in_range(result, op)
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn primary(&mut self) -> Result<Quantity, ParseError> {
+        match self.peek() {
+            Some('(') => {
+                self.bump();
+                let ret = self.sum()?;
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(')') => {
+                        self.bump();
+                        Ok(ret)
+                    }
+                    Some(c) => Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+                    None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if is_unit_char(c) => self.unit(),
+            Some(c) => Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    /// A number, or a ratio of two numbers written `1|3`
+    fn number(&mut self) -> Result<Quantity, ParseError> {
+        let mut value = self.decimal()?;
+        if self.peek() == Some('|') {
+            self.bump();
+            value /= self.decimal()?;
+        }
+        Ok(Quantity::new(value, DynDimension::NONE))
+    }
+
+    fn decimal(&mut self) -> Result<f64, ParseError> {
+        let start = self.position;
+        self.eat_while(|c| c.is_ascii_digit() || c == '.');
+        // Only treat an `e` as an exponent if digits follow, `3 eV` is three electronvolts
+        let rest = &self.input[self.position..];
+        if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+            let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            if digits.starts_with(|c: char| c.is_ascii_digit()) {
+                self.position += rest.len() - digits.len();
+                self.eat_while(|c| c.is_ascii_digit());
+            }
+        }
+        self.input[start..self.position]
+            .parse()
+            .map_err(|_| ParseError {
+                position: start,
+                kind: ParseErrorKind::InvalidNumber,
+            })
+    }
+
+    fn integer(&mut self) -> Result<i32, ParseError> {
+        let start = self.position;
+        if self.peek() == Some('-') {
+            self.bump();
+        }
+        self.eat_while(|c| c.is_ascii_digit());
+        self.input[start..self.position]
+            .parse()
+            .map_err(|_| ParseError {
+                position: start,
+                kind: ParseErrorKind::InvalidNumber,
+            })
+    }
+
+    fn unit(&mut self) -> Result<Quantity, ParseError> {
+        let start = self.position;
+        self.eat_while(is_unit_char);
+        let name = &self.input[start..self.position];
+        let unit = (self.resolve)(name).ok_or_else(|| ParseError {
+            position: start,
+            kind: ParseErrorKind::UnknownUnit(name.to_string()),
+        })?;
+        if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            let exponent = self.position;
+            let result = unit.checked_powi(self.integer()?);
+            self.in_range(result, exponent)
+        } else {
+            Ok(unit)
+        }
+    }
+
+    fn in_range(&self, result: Option<Quantity>, position: usize) -> Result<Quantity, ParseError> {
+        result.ok_or(ParseError {
+            position,
+            kind: ParseErrorKind::ExponentOutOfRange,
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn eat_while(&mut self, f: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.eat_while(char::is_whitespace);
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            position: self.position,
+            kind,
+        }
+    }
+}
+
+fn is_unit_char(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn si(input: &str) -> f64 {
+        input.parse::<Quantity>().unwrap().value()
+    }
+
+    #[test]
+    fn expressions() {
+        assert!((si("60 mi/h") - 26.8224).abs() < 1e-12);
+        assert_eq!(si("3 kN*m"), 3000.0);
+        assert_eq!(si("2 cm3"), si("2 (cm)^3"));
+        assert!((si("2 cm3") - 2e-6).abs() < 1e-18);
+        assert_eq!(si("1.5e3 g"), 1.5);
+        assert_eq!(si("1|4 m"), 0.25);
+        assert_eq!(si("3 eV") / si("eV"), 3.0);
+        assert_eq!(si("1 J/kg K"), si("1 J/(kg*K)"));
+        assert_eq!(si("-(2 m + 50 cm)^2"), -6.25);
+        assert_eq!(
+            "9.81 m s^-2".parse::<Quantity>().unwrap().dimension(),
+            DynDimension::from_ints([1, -2, 0, 0, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let err = "1 m + 2 s".parse::<Quantity>().unwrap_err();
+        assert_eq!(err.position, 4);
+        assert!(matches!(err.kind, ParseErrorKind::Dimension(_)));
+        assert_eq!(
+            "3 furlongs".parse::<Quantity>().unwrap_err(),
+            ParseError {
+                position: 2,
+                kind: ParseErrorKind::UnknownUnit("furlongs".to_string())
+            }
+        );
+        assert_eq!(
+            "(1 m".parse::<Quantity>().unwrap_err().kind,
+            ParseErrorKind::UnexpectedEnd
+        );
+        assert_eq!("1 m )".parse::<Quantity>().unwrap_err().position, 4);
+        for (input, position) in [
+            ("(m^65536)^65536", 9),
+            ("m2147483647 m", 12),
+            ("m^-2147483648 / m", 14),
+        ] {
+            assert_eq!(
+                input.parse::<Quantity>().unwrap_err(),
+                ParseError {
+                    position,
+                    kind: ParseErrorKind::ExponentOutOfRange
+                },
+                "{}",
+                input
+            );
+        }
+    }
+}
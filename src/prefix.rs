@@ -1,21 +1,23 @@
 use std::{
     fmt::Debug,
     marker::PhantomData,
-    ops::{Add, Mul, Div},
+    ops::{Add, Div, Mul, Sub},
 };
 use typenum::{op, Integer, ToInt};
 
-use crate::{base::Unitless, Unit, Value, UnitValue};
+use crate::{Unit, Value, UnitValue};
 
+/// The unit `U` scaled by `10^-Power`. Only a marker: it has no size, so a `Value` has the
+/// layout of its number.
 #[derive(Clone, Copy)]
-pub struct Prefix<Power, U>(pub(crate) U, pub(crate) PhantomData<Power>);
+pub struct Prefix<Power, U>(pub(crate) PhantomData<U>, pub(crate) PhantomData<Power>);
 
-impl<P: ToInt<i32>, U: Debug> Debug for Prefix<P, U> {
+impl<P: ToInt<i32>, U: Unit + Debug> Debug for Prefix<P, U> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.name() {
-            Some("") => write!(f, "{:?}", self.0),
-            Some(name) => write!(f, "{name} {:?}", self.0),
-            None => write!(f, "{:?} x10^{}", self.0, P::to_int()),
+            Some("") => write!(f, "{:?}", U::create()),
+            Some(name) => write!(f, "{name} {:?}", U::create()),
+            None => write!(f, "{:?} x10^{}", U::create(), P::to_int()),
         }
     }
 }
@@ -26,19 +28,19 @@ where
     UP: Add<VP>,
 {
     type Output = Prefix<op!(UP + VP), op!(U * V)>;
-    fn mul(self, rhs: Prefix<VP, V>) -> Self::Output {
-        Prefix(self.0 * rhs.0, PhantomData)
+    fn mul(self, _rhs: Prefix<VP, V>) -> Self::Output {
+        Prefix(PhantomData, PhantomData)
     }
 }
 
-impl<UP: Integer, VP: Integer, U: Unit, V: Unit> Mul<Prefix<VP, V>> for Prefix<UP, U>
+impl<UP: Integer, VP: Integer, U: Unit, V: Unit> Div<Prefix<VP, V>> for Prefix<UP, U>
 where
-    U: Mul<V>,
-    UP: Add<VP>,
+    U: Div<V>,
+    UP: Sub<VP>,
 {
-    type Output = Prefix<op!(UP + VP), op!(U * V)>;
-    fn mul(self, rhs: Prefix<VP, V>) -> Self::Output {
-        Prefix(self.0 * rhs.0, PhantomData)
+    type Output = Prefix<op!(UP - VP), op!(U / V)>;
+    fn div(self, _rhs: Prefix<VP, V>) -> Self::Output {
+        Prefix(PhantomData, PhantomData)
     }
 }
 
@@ -49,49 +51,65 @@ impl<Power: ToInt<i32>, U> Prefix<Power, U> {
 
     pub fn name(&self) -> Option<&'static str> {
         match self.power() {
-            -24 => Some("Yotta"),
-            -21 => Some("Zetta"),
-            -18 => Some("Exa"),
-            -15 => Some("Peta"),
-            -12 => Some("Tera"),
-            -9 => Some("Giga"),
-            -6 => Some("Mega"),
-            -3 => Some("Kilo"),
-            -2 => Some("Hecta"),
-            -1 => Some("Deka"),
             0 => Some(""),
-            1 => Some("Deci"),
-            2 => Some("Centi"),
-            3 => Some("Milli"),
-            6 => Some("Micro"),
-            9 => Some("Nano"),
-            12 => Some("Pico"),
-            15 => Some("Fempto"),
-            18 => Some("Atto"),
-            21 => Some("Zepto"),
-            24 => Some("Yocto"),
-            _ => None,
+            power => PREFIXES
+                .iter()
+                .find(|(_, _, p)| *p == power)
+                .map(|(name, _, _)| *name),
         }
     }
 }
 
+/// Every named prefix as `(name, symbol, power)`. `power` follows the convention of the `Power`
+/// of a `Prefix`, so `Kilo` is `-3`.
+pub const PREFIXES: [(&str, &str, i32); 20] = [
+    ("Yotta", "Y", -24),
+    ("Zetta", "Z", -21),
+    ("Exa", "E", -18),
+    ("Peta", "P", -15),
+    ("Tera", "T", -12),
+    ("Giga", "G", -9),
+    ("Mega", "M", -6),
+    ("Kilo", "k", -3),
+    ("Hecto", "h", -2),
+    ("Deka", "da", -1),
+    ("Deci", "d", 1),
+    ("Centi", "c", 2),
+    ("Milli", "m", 3),
+    ("Micro", "u", 6),
+    ("Nano", "n", 9),
+    ("Pico", "p", 12),
+    ("Femto", "f", 15),
+    ("Atto", "a", 18),
+    ("Zepto", "z", 21),
+    ("Yocto", "y", 24),
+];
+
 impl<Power: Integer, U: Unit> Prefix<Power, U> {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new<V>(val: V) -> Value<V, Power, U> {
         Value::new(val)
     }
 }
 
 impl<Power: ToInt<i32>, U: Unit> Prefix<Power, U> {
-    pub fn convert<V: UnitValue, RhsPower: ToInt<i32>, RhsUnits: Unit>(
+    pub fn convert<V: UnitValue, RhsPower: ToInt<i32>, RhsUnits: Unit<Dimension = U::Dimension>>(
         &self,
         value: V,
         _rhs: &Prefix<RhsPower, RhsUnits>,
-    ) -> V
-    where
-        U: Div<RhsUnits, Output = Unitless>,
-    {
-        let power = Power::to_int() - RhsPower::to_int();
-        value.apply_prefix(-power)
+    ) -> V {
+        let power = (Power::to_int() + U::SCALE) - (RhsPower::to_int() + RhsUnits::SCALE);
+        value.rescale(-power, U::factor() / RhsUnits::factor())
+    }
+
+    /// `convert` for every element of `values`, in place
+    pub fn convert_all<V: UnitValue, RhsPower: ToInt<i32>, RhsUnits: Unit<Dimension = U::Dimension>>(
+        &self,
+        values: &mut [V],
+        _rhs: &Prefix<RhsPower, RhsUnits>,
+    ) {
+        let power = (Power::to_int() + U::SCALE) - (RhsPower::to_int() + RhsUnits::SCALE);
+        V::rescale_all(values, -power, U::factor() / RhsUnits::factor())
     }
 }
 
@@ -103,7 +121,9 @@ pub type Tera<U> = Prefix<typenum::consts::N12, U>;
 pub type Giga<U> = Prefix<typenum::consts::N9, U>;
 pub type Mega<U> = Prefix<typenum::consts::N6, U>;
 pub type Kilo<U> = Prefix<typenum::consts::N3, U>;
-pub type Hecta<U> = Prefix<typenum::consts::N2, U>;
+pub type Hecto<U> = Prefix<typenum::consts::N2, U>;
+/// The old spelling of `Hecto`
+pub type Hecta<U> = Hecto<U>;
 pub type Deka<U> = Prefix<typenum::consts::N1, U>;
 pub type Base<U> = Prefix<typenum::consts::Z0, U>;
 pub type Deci<U> = Prefix<typenum::consts::P1, U>;
@@ -112,7 +132,9 @@ pub type Milli<U> = Prefix<typenum::consts::P3, U>;
 pub type Micro<U> = Prefix<typenum::consts::P6, U>;
 pub type Nano<U> = Prefix<typenum::consts::P9, U>;
 pub type Pico<U> = Prefix<typenum::consts::P12, U>;
-pub type Fempto<U> = Prefix<typenum::consts::P15, U>;
+pub type Femto<U> = Prefix<typenum::consts::P15, U>;
+/// The old spelling of `Femto`
+pub type Fempto<U> = Femto<U>;
 pub type Atto<U> = Prefix<typenum::consts::P18, U>;
 pub type Zepto<U> = Prefix<typenum::consts::P21, U>;
 pub type Yocto<U> = Prefix<typenum::consts::P24, U>;
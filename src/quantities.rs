@@ -0,0 +1,256 @@
+//! Buffers of many values sharing one unit, e.g. a block of sensor samples.
+//!
+//! `Quantities` stores the plain numbers contiguously and the unit once, in the type. Converting
+//! the whole buffer computes the scale once (see `UnitValue::rescale_all`) and rescales in place,
+//! instead of going through `Value::convert` per element.
+
+use std::{
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use typenum::{Diff, Integer, Sum, ToInt};
+
+use crate::{kind::CombineKind, prefix::Prefix, DivUnit, MulUnit, Unit, UnitValue, Value};
+
+/// Values `V` in the unit `U` scaled by the prefix `10^-P`, like `Vec<Value<V, P, U, K>>`
+pub struct Quantities<V, P: Integer, U: Unit, K = crate::kind::AnyKind>(
+    Vec<V>,
+    PhantomData<(P, U, K)>,
+);
+
+impl<V: Clone, P: Integer, U: Unit, K> Clone for Quantities<V, P, U, K> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<V: std::fmt::Debug, P: Integer, U: Unit, K> std::fmt::Debug for Quantities<V, P, U, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Quantities({:?})", self.0)
+    }
+}
+
+impl<V: PartialEq, P: Integer, U: Unit, K> PartialEq for Quantities<V, P, U, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<V, P: Integer, U: Unit, K> Quantities<V, P, U, K> {
+    /// Takes the plain numbers, in the unit of the buffer
+    pub fn new(values: Vec<V>) -> Self {
+        Self(values, PhantomData)
+    }
+
+    pub fn values(&self) -> &[V] {
+        &self.0
+    }
+
+    pub fn values_mut(&mut self) -> &mut [V] {
+        &mut self.0
+    }
+
+    pub fn into_values(self) -> Vec<V> {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn push(&mut self, value: Value<V, P, U, K>) {
+        self.0.push(value.0);
+    }
+
+    /// The unit is in the type, so this is only a copy of the number
+    pub fn get(&self, index: usize) -> Option<Value<V, P, U, K>>
+    where
+        V: Clone,
+    {
+        self.0.get(index).cloned().map(Value::new)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Value<V, P, U, K>> + '_
+    where
+        V: Clone,
+    {
+        self.0.iter().cloned().map(Value::new)
+    }
+}
+
+impl<V: UnitValue, P: Integer + ToInt<i32>, U: Unit, K> Quantities<V, P, U, K> {
+    /// Converts every element in place, computing the scale once
+    pub fn convert<TargetP: Integer + ToInt<i32>, TargetU: Unit<Dimension = U::Dimension>>(
+        mut self,
+    ) -> Quantities<V, TargetP, TargetU, K> {
+        let target = Prefix::<TargetP, TargetU>(PhantomData, PhantomData);
+        Prefix::<P, U>(PhantomData, PhantomData).convert_all(&mut self.0, &target);
+        Quantities::new(self.0)
+    }
+
+    /// Expresses every element in the coherent SI unit of its dimension, without a prefix
+    pub fn to_base(self) -> Quantities<V, typenum::Z0, U::Base, K> {
+        self.convert()
+    }
+}
+
+impl<V, P: Integer, U: Unit, K> FromIterator<Value<V, P, U, K>> for Quantities<V, P, U, K> {
+    fn from_iter<I: IntoIterator<Item = Value<V, P, U, K>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().map(|value| value.0).collect())
+    }
+}
+
+impl<V, P: Integer, U: Unit, K> From<Vec<V>> for Quantities<V, P, U, K> {
+    fn from(values: Vec<V>) -> Self {
+        Self::new(values)
+    }
+}
+
+/// Applies `op` to the elements pairwise. Panics if the lengths differ.
+fn zip_with<L, R, O>(lhs: Vec<L>, rhs: Vec<R>, op: impl Fn(L, R) -> O) -> Vec<O> {
+    assert_eq!(
+        lhs.len(),
+        rhs.len(),
+        "quantity buffers of different lengths"
+    );
+    lhs.into_iter().zip(rhs).map(|(l, r)| op(l, r)).collect()
+}
+
+/// Element-wise, in the units of `rhs` like `Value` addition. Panics if the lengths differ.
+impl<LhsV, RhsV, LhsP, RhsP, LhsU, RhsU, K> Add<Quantities<RhsV, RhsP, RhsU, K>>
+    for Quantities<LhsV, LhsP, LhsU, K>
+where
+    LhsV: UnitValue + Add<RhsV>,
+    LhsP: Integer + ToInt<i32>,
+    RhsP: Integer + ToInt<i32>,
+    LhsU: Unit,
+    RhsU: Unit<Dimension = LhsU::Dimension>,
+{
+    type Output = Quantities<LhsV::Output, RhsP, RhsU, K>;
+    fn add(self, rhs: Quantities<RhsV, RhsP, RhsU, K>) -> Self::Output {
+        let lhs = self.convert::<RhsP, RhsU>();
+        Quantities::new(zip_with(lhs.0, rhs.0, Add::add))
+    }
+}
+
+/// Element-wise, in the units of `rhs` like `Value` subtraction. Panics if the lengths differ.
+impl<LhsV, RhsV, LhsP, RhsP, LhsU, RhsU, K> Sub<Quantities<RhsV, RhsP, RhsU, K>>
+    for Quantities<LhsV, LhsP, LhsU, K>
+where
+    LhsV: UnitValue + Sub<RhsV>,
+    LhsP: Integer + ToInt<i32>,
+    RhsP: Integer + ToInt<i32>,
+    LhsU: Unit,
+    RhsU: Unit<Dimension = LhsU::Dimension>,
+{
+    type Output = Quantities<LhsV::Output, RhsP, RhsU, K>;
+    fn sub(self, rhs: Quantities<RhsV, RhsP, RhsU, K>) -> Self::Output {
+        let lhs = self.convert::<RhsP, RhsU>();
+        Quantities::new(zip_with(lhs.0, rhs.0, Sub::sub))
+    }
+}
+
+/// Element-wise, in the product unit like `Value` multiplication. Panics if the lengths differ.
+impl<LhsV, RhsV, LhsP, RhsP, LhsU, RhsU, LhsK, RhsK> Mul<Quantities<RhsV, RhsP, RhsU, RhsK>>
+    for Quantities<LhsV, LhsP, LhsU, LhsK>
+where
+    LhsV: Mul<RhsV>,
+    LhsV::Output: UnitValue,
+    LhsP: Integer + Add<RhsP>,
+    RhsP: Integer,
+    Sum<LhsP, RhsP>: Integer,
+    LhsU: Unit,
+    RhsU: Unit,
+    LhsU::Dimension: Add<RhsU::Dimension>,
+    LhsK: CombineKind<RhsK>,
+{
+    type Output = Quantities<LhsV::Output, Sum<LhsP, RhsP>, MulUnit<LhsU, RhsU>, LhsK::Output>;
+    fn mul(self, rhs: Quantities<RhsV, RhsP, RhsU, RhsK>) -> Self::Output {
+        let mut values = zip_with(self.0, rhs.0, Mul::mul);
+        let (power, factor) = (LhsU::SCALE + RhsU::SCALE, LhsU::factor() * RhsU::factor());
+        UnitValue::rescale_all(&mut values, -power, factor);
+        Quantities::new(values)
+    }
+}
+
+/// Element-wise, in the quotient unit like `Value` division. Panics if the lengths differ.
+impl<LhsV, RhsV, LhsP, RhsP, LhsU, RhsU, LhsK, RhsK> Div<Quantities<RhsV, RhsP, RhsU, RhsK>>
+    for Quantities<LhsV, LhsP, LhsU, LhsK>
+where
+    LhsV: Div<RhsV>,
+    LhsV::Output: UnitValue,
+    LhsP: Integer + Sub<RhsP>,
+    RhsP: Integer,
+    Diff<LhsP, RhsP>: Integer,
+    LhsU: Unit,
+    RhsU: Unit,
+    LhsU::Dimension: Sub<RhsU::Dimension>,
+    LhsK: CombineKind<RhsK>,
+{
+    type Output = Quantities<LhsV::Output, Diff<LhsP, RhsP>, DivUnit<LhsU, RhsU>, LhsK::Output>;
+    fn div(self, rhs: Quantities<RhsV, RhsP, RhsU, RhsK>) -> Self::Output {
+        let mut values = zip_with(self.0, rhs.0, Div::div);
+        let (power, factor) = (LhsU::SCALE - RhsU::SCALE, LhsU::factor() / RhsU::factor());
+        UnitValue::rescale_all(&mut values, -power, factor);
+        Quantities::new(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Coherent, Meter, Second},
+        dimension::Speed,
+        prefix::Milli,
+    };
+    use typenum::{N3, P3, Z0};
+
+    #[test]
+    fn bulk_conversion() {
+        let samples: Quantities<f64, P3, Meter> =
+            (0..1000).map(|i| Milli::<Meter>::new(i as f64)).collect();
+        let meters = samples.clone().convert::<Z0, Meter>();
+        assert_eq!(meters.len(), 1000);
+        for (sample, meter) in samples.iter().zip(meters.iter()) {
+            assert!((*sample.convert::<Z0, Meter>().value() - meter.value()).abs() < 1e-15);
+        }
+        let km = meters.convert::<N3, Meter>();
+        assert_eq!(km.get(500).map(|v| *v.value()), Some(5e-4));
+
+        let mut exact = Quantities::<i32, Z0, Meter>::new(vec![1, 2]);
+        exact.push(Value::new(3));
+        assert_eq!(exact.values(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let distance = Quantities::<f64, N3, Meter>::new(vec![1.0, 3.0, 0.5]);
+        let offset = Quantities::<f64, Z0, Meter>::new(vec![500.0, 0.0, 250.0]);
+        assert_eq!(
+            (distance.clone() + offset).into_values(),
+            [1500.0, 3000.0, 750.0]
+        );
+
+        let time = Quantities::<f64, Z0, Second>::new(vec![100.0, 1000.0, 10.0]);
+        let speed: Quantities<f64, N3, Coherent<Speed>> = distance / time;
+        assert_eq!(speed.to_base().into_values(), [10.0, 3.0, 50.0]);
+
+        let area = Quantities::<f64, N3, Meter>::new(vec![2.0])
+            * Quantities::<f64, N3, Meter>::new(vec![3.0]);
+        assert_eq!(area.to_base().values(), [6e6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn length_mismatch() {
+        let _ = Quantities::<f64, Z0, Second>::new(vec![1.0])
+            + Quantities::<f64, Z0, Second>::new(vec![]);
+    }
+}
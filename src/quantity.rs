@@ -0,0 +1,433 @@
+use std::{
+    convert::TryFrom,
+    fmt::{Debug, Display},
+    ops::{Div, Mul},
+};
+
+use typenum::{Integer, ToInt};
+
+use crate::{dimension::Dimension, Unit, Value};
+
+/// Symbols of the coherent SI units, in the order of `dimension::Dim`
+pub const BASE_SYMBOLS: [&str; 7] = ["m", "s", "kg", "mol", "A", "K", "cd"];
+/// Names of the base quantities, in the order of `dimension::Dim`
+pub const BASE_QUANTITIES: [&str; 7] = [
+    "Length",
+    "Time",
+    "Mass",
+    "Amount",
+    "Current",
+    "Tempature",
+    "LuminousIntesity",
+];
+
+/// The dimension of a `Quantity`, only known at runtime. The exponents are `(numerator,
+/// denominator)` pairs in the order of `dimension::Dim`, always kept in lowest terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynDimension([(i32, i32); 7]);
+
+impl DynDimension {
+    pub const NONE: Self = Self([(0, 1); 7]);
+
+    /// A dimension with whole exponents
+    pub const fn from_ints(exponents: [i32; 7]) -> Self {
+        let mut ret = [(0, 1); 7];
+        let mut i = 0;
+        while i < 7 {
+            ret[i] = (exponents[i], 1);
+            i += 1;
+        }
+        Self(ret)
+    }
+
+    /// A dimension with exponents given in halves, so `[3, 0, ...]` is `Length^(3/2)`
+    pub const fn from_halves(halves: [i32; 7]) -> Self {
+        let mut ret = [(0, 1); 7];
+        let mut i = 0;
+        while i < 7 {
+            ret[i] = if halves[i] % 2 == 0 {
+                (halves[i] / 2, 1)
+            } else {
+                (halves[i], 2)
+            };
+            i += 1;
+        }
+        Self(ret)
+    }
+
+    /// The dimension of the `i`th base quantity
+    pub const fn base(i: usize) -> Self {
+        let mut ret = [0; 7];
+        ret[i] = 1;
+        Self::from_ints(ret)
+    }
+
+    /// The runtime equivalent of a type level `Dim`
+    pub fn of<D: Dimension>() -> Self {
+        Self::from_ratios(D::exponents())
+    }
+
+    pub fn from_ratios(exponents: [(i32, i32); 7]) -> Self {
+        Self(exponents.map(|(num, den)| {
+            reduce(num.into(), den.into()).expect("dimension exponent with a zero denominator")
+        }))
+    }
+
+    pub fn exponents(&self) -> [(i32, i32); 7] {
+        self.0
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Panics if an exponent overflows, see `checked_powi`
+    pub fn powi(self, n: i32) -> Self {
+        self.checked_powi(n).expect(OUT_OF_RANGE)
+    }
+
+    /// Panics if an exponent overflows or `n` is zero, see `checked_root`
+    pub fn root(self, n: i32) -> Self {
+        self.checked_root(n).expect(OUT_OF_RANGE)
+    }
+
+    /// `None` if an exponent doesn't fit in an `i32`
+    pub fn checked_powi(self, n: i32) -> Option<Self> {
+        self.map(|num, den| (num * i128::from(n), den))
+    }
+
+    /// `None` if an exponent doesn't fit in an `i32` or `n` is zero
+    pub fn checked_root(self, n: i32) -> Option<Self> {
+        self.map(|num, den| (num, den * i128::from(n)))
+    }
+
+    /// `None` if an exponent doesn't fit in an `i32`
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let mut ret = self.0;
+        for (l, (num, den)) in ret.iter_mut().zip(rhs.0) {
+            let (l_num, l_den) = (i128::from(l.0), i128::from(l.1));
+            *l = reduce(
+                l_num * i128::from(den) + i128::from(num) * l_den,
+                l_den * i128::from(den),
+            )?;
+        }
+        Some(Self(ret))
+    }
+
+    /// `None` if an exponent doesn't fit in an `i32`
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(rhs.checked_powi(-1)?)
+    }
+
+    /// Applies `f` to every exponent, widened so it can't overflow before `reduce` checks it
+    fn map(self, f: impl Fn(i128, i128) -> (i128, i128)) -> Option<Self> {
+        let mut ret = self.0;
+        for exponent in &mut ret {
+            let (num, den) = f(exponent.0.into(), exponent.1.into());
+            *exponent = reduce(num, den)?;
+        }
+        Some(Self(ret))
+    }
+
+    /// Lists the base quantities this dimension is made of, e.g. `Length^2 Mass Time^-2`
+    pub fn breakdown(&self) -> String {
+        self.join(&BASE_QUANTITIES, "Dimensionless")
+    }
+
+    /// Formats the coherent SI unit of this dimension as it would be typeset, e.g. `m/s²` or
+    /// `m²·kg/s²`
+    pub fn pretty(&self) -> String {
+        fn part(symbol: &str, (num, den): (i32, i32)) -> String {
+            const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+            match (num, den) {
+                (1, 1) => symbol.to_string(),
+                (num, 1) => symbol
+                    .chars()
+                    .chain(num.to_string().chars().map(|c| match c.to_digit(10) {
+                        Some(digit) => SUPERSCRIPTS[digit as usize],
+                        None => '⁻',
+                    }))
+                    .collect(),
+                (num, den) => format!("{symbol}^({num}/{den})"),
+            }
+        }
+        let (numerator, denominator): (Vec<_>, Vec<_>) = BASE_SYMBOLS
+            .iter()
+            .zip(self.0)
+            .filter(|(_, (num, _))| *num != 0)
+            .partition(|(_, (num, _))| *num > 0);
+        let numerator: Vec<_> = numerator.into_iter().map(|(s, exp)| part(s, exp)).collect();
+        let denominator: Vec<_> = denominator
+            .into_iter()
+            .map(|(s, (num, den))| part(s, (-num, den)))
+            .collect();
+        let numerator = match (numerator.is_empty(), denominator.is_empty()) {
+            (true, true) => return String::new(),
+            (true, false) => "1".to_string(),
+            _ => numerator.join("·"),
+        };
+        match denominator.len() {
+            0 => numerator,
+            1 => format!("{}/{}", numerator, denominator[0]),
+            _ => format!("{}/({})", numerator, denominator.join("·")),
+        }
+    }
+
+    fn join(&self, names: &[&str; 7], empty: &str) -> String {
+        let parts: Vec<_> = names
+            .iter()
+            .zip(self.0)
+            .filter(|(_, (num, _))| *num != 0)
+            .map(|(name, exp)| match exp {
+                (1, 1) => name.to_string(),
+                (num, 1) => format!("{name}^{num}"),
+                (num, den) => format!("{name}^({num}/{den})"),
+            })
+            .collect();
+        if parts.is_empty() {
+            empty.to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+const OUT_OF_RANGE: &str = "dimension exponent out of range";
+
+/// `num / den` in lowest terms with a positive denominator, `None` if it doesn't fit in `i32`s
+fn reduce(num: i128, den: i128) -> Option<(i32, i32)> {
+    fn gcd(a: i128, b: i128) -> i128 {
+        if b == 0 {
+            a.abs()
+        } else {
+            gcd(b, a % b)
+        }
+    }
+    if den == 0 {
+        return None;
+    }
+    let g = gcd(num, den).max(1) * den.signum();
+    Some((i32::try_from(num / g).ok()?, i32::try_from(den / g).ok()?))
+}
+
+/// Panics if an exponent overflows, see `checked_mul`
+impl Mul for DynDimension {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).expect(OUT_OF_RANGE)
+    }
+}
+
+/// Panics if an exponent overflows, see `checked_div`
+impl Div for DynDimension {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        self.checked_div(rhs).expect(OUT_OF_RANGE)
+    }
+}
+
+/// Formats the coherent SI unit of this dimension, e.g. `m^2 kg s^-2`
+impl Display for DynDimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.join(&BASE_SYMBOLS, ""))
+    }
+}
+
+/// The dimensions of the two sides of an operation don't match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionError {
+    pub expected: DynDimension,
+    pub found: DynDimension,
+}
+
+impl Display for DimensionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "conformability error: {} is not {}",
+            self.found.breakdown(),
+            self.expected.breakdown()
+        )
+    }
+}
+
+impl std::error::Error for DimensionError {}
+
+/// A value whose dimension is only known at runtime, stored in coherent SI units.
+///
+/// This is what text is parsed into (see `parse`); `Quantity::to_value` checks the dimension
+/// and turns it into a statically typed `Value`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Quantity {
+    value: f64,
+    dimension: DynDimension,
+}
+
+impl Quantity {
+    pub const fn new(value: f64, dimension: DynDimension) -> Self {
+        Self { value, dimension }
+    }
+
+    /// The value in the coherent SI unit of `dimension()`
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn dimension(&self) -> DynDimension {
+        self.dimension
+    }
+
+    pub fn powi(self, n: i32) -> Self {
+        Self::new(self.value.powi(n), self.dimension.powi(n))
+    }
+
+    /// `None` if an exponent of the dimension overflows
+    pub fn checked_powi(self, n: i32) -> Option<Self> {
+        let dimension = self.dimension.checked_powi(n)?;
+        Some(Self::new(self.value.powi(n), dimension))
+    }
+
+    /// `None` if an exponent of the dimension overflows
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let dimension = self.dimension.checked_mul(rhs.dimension)?;
+        Some(Self::new(self.value * rhs.value, dimension))
+    }
+
+    /// `None` if an exponent of the dimension overflows
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        let dimension = self.dimension.checked_div(rhs.dimension)?;
+        Some(Self::new(self.value / rhs.value, dimension))
+    }
+
+    pub fn try_add(self, rhs: Self) -> Result<Self, DimensionError> {
+        self.check(&rhs)?;
+        Ok(Self::new(self.value + rhs.value, self.dimension))
+    }
+
+    pub fn try_sub(self, rhs: Self) -> Result<Self, DimensionError> {
+        self.check(&rhs)?;
+        Ok(Self::new(self.value - rhs.value, self.dimension))
+    }
+
+    /// How many `unit`s this quantity is
+    pub fn value_in(&self, unit: &Self) -> Result<f64, DimensionError> {
+        self.check(unit)?;
+        Ok(self.value / unit.value)
+    }
+
+    /// Converts into a statically typed value, if the dimension matches
+    pub fn to_value<P, U, K>(&self) -> Result<Value<f64, P, U, K>, DimensionError>
+    where
+        P: Integer + ToInt<i32>,
+        U: Unit,
+        U::Dimension: Dimension,
+    {
+        self.check(&Self::new(1.0, DynDimension::of::<U::Dimension>()))?;
+        Ok(Value::from_base(Value::new(self.value)))
+    }
+
+    fn check(&self, expected: &Self) -> Result<(), DimensionError> {
+        if self.dimension == expected.dimension {
+            Ok(())
+        } else {
+            Err(DimensionError {
+                expected: expected.dimension,
+                found: self.dimension,
+            })
+        }
+    }
+}
+
+impl<P, U, K> From<Value<f64, P, U, K>> for Quantity
+where
+    P: Integer + ToInt<i32>,
+    U: Unit,
+    U::Dimension: Dimension,
+{
+    fn from(value: Value<f64, P, U, K>) -> Self {
+        Self::new(*value.to_base().value(), DynDimension::of::<U::Dimension>())
+    }
+}
+
+impl Mul for Quantity {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(self.value * rhs.value, self.dimension * rhs.dimension)
+    }
+}
+
+impl Div for Quantity {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Self::new(self.value / rhs.value, self.dimension / rhs.dimension)
+    }
+}
+
+impl Mul<f64> for Quantity {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.value * rhs, self.dimension)
+    }
+}
+
+impl Debug for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.dimension.is_dimensionless() {
+            Display::fmt(&self.value, f)
+        } else {
+            Display::fmt(&self.value, f)?;
+            write!(f, " {}", self.dimension)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Gram, Meter, Second},
+        prefix::Kilo,
+    };
+    use typenum::{P3, Z0};
+
+    #[test]
+    fn static_round_trip() {
+        let speed = Quantity::from(Kilo::<Meter>::new(36f64) / Second::new(3600));
+        assert_eq!(speed.value(), 10.0);
+        assert_eq!(
+            speed.dimension(),
+            DynDimension::of::<crate::dimension::Speed>()
+        );
+        assert_eq!(format!("{}", speed), "10 m s^-1");
+        assert_eq!(
+            speed
+                .to_value::<Z0, crate::base::Coherent<crate::dimension::Speed>, _>()
+                .unwrap(),
+            Meter::new(10) / Second::new(1)
+        );
+        let mass = Quantity::from(Gram::new(1500));
+        assert_eq!(mass.to_value::<Z0, Gram, _>().unwrap(), Gram::new(1500));
+        assert!(mass.to_value::<P3, Meter, crate::kind::AnyKind>().is_err());
+    }
+
+    #[test]
+    fn dimensions() {
+        let hz = DynDimension::base(1).powi(-1);
+        assert_eq!(hz.root(2).exponents()[1], (-1, 2));
+        assert_eq!(hz.root(2) * hz.root(2), hz);
+        assert_eq!((hz / hz), DynDimension::NONE);
+        assert_eq!(
+            (DynDimension::base(2) * DynDimension::base(0) / hz.powi(-2)).breakdown(),
+            "Length Time^-2 Mass"
+        );
+        assert_eq!(DynDimension::of::<crate::dimension::Acceleration>().pretty(), "m/s²");
+        assert_eq!(DynDimension::of::<crate::dimension::Pressure>().pretty(), "kg/(m·s²)");
+        assert_eq!(hz.pretty(), "1/s");
+    }
+}
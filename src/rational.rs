@@ -0,0 +1,90 @@
+//! Exact arithmetic with arbitrary precision rationals, behind the `bigrational` feature.
+//!
+//! `UnitValue` is implemented for `BigRational`, so prefix and scale conversions multiply by
+//! exact powers of ten, including Yotta and Yocto which are out of reach of an `f64`. The size
+//! of a unit outside the decimal SI family (see `Unit::factor`) is an `f64`; it is taken at its
+//! exact binary value, so such conversions are only as exact as that factor.
+
+pub use num_bigint::BigInt;
+pub use num_rational::BigRational;
+
+use std::convert::TryFrom;
+
+use crate::UnitValue;
+
+impl UnitValue for BigRational {
+    fn apply_prefix(&self, power_of_ten: i32) -> Self {
+        if power_of_ten == 0 {
+            return self.clone();
+        }
+        self * BigRational::from_integer(BigInt::from(10)).pow(power_of_ten)
+    }
+
+    /// Panics if `factor` is not finite
+    fn apply_factor(&self, factor: f64) -> Self {
+        self * BigRational::from_float(factor).expect("unit factors are finite")
+    }
+}
+
+/// Parses a decimal number such as `-12.5e-3` exactly, unlike going through an `f64`
+pub fn decimal(s: &str) -> Option<BigRational> {
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(index) => (&s[..index], s[index + 1..].parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let unsigned = int.trim_start_matches(['+', '-']);
+    if unsigned.len() + frac.len() == 0
+        || !unsigned
+            .chars()
+            .chain(frac.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let digits: BigInt = format!("{}{}", int, frac).parse().ok()?;
+    let power = exponent.checked_sub(i32::try_from(frac.len()).ok()?)?;
+    Some(BigRational::from_integer(digits).apply_prefix(power))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Gram, Meter},
+        prefix::{Kilo, Yotta},
+        Value,
+    };
+    use typenum::Z0;
+
+    #[test]
+    fn exact_prefixes() {
+        let huge = Yotta::<Meter>::new(decimal("1.5").unwrap());
+        let tiny: Value<BigRational, _, Meter> = huge.convert::<typenum::P24, Meter>();
+        assert_eq!(*tiny.value(), decimal("1.5e48").unwrap());
+        assert_eq!(tiny.convert::<typenum::N24, Meter>(), huge);
+        assert_eq!(
+            *tiny.to_base().value(),
+            decimal("1500000000000000000000000").unwrap()
+        );
+
+        // 0.1 kg has no exact binary representation
+        let a = Kilo::<Gram>::new(decimal("0.1").unwrap());
+        let b = Value::<_, Z0, Gram>::new(decimal("200").unwrap());
+        assert_eq!(*(a + b.clone()).value(), decimal("300").unwrap());
+        assert_eq!(*(b.clone() * b).value(), decimal("4e-2").unwrap());
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(
+            decimal("-12.5e-3"),
+            Some(BigRational::new((-1).into(), 80.into()))
+        );
+        assert_eq!(decimal(".5"), Some(BigRational::new(1.into(), 2.into())));
+        assert_eq!(decimal("7"), Some(BigRational::from_integer(7.into())));
+        for invalid in ["", "-", ".", "1.2.3", "1e", "e5", "1x"] {
+            assert_eq!(decimal(invalid), None, "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,475 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    sync::{OnceLock, RwLock, RwLockReadGuard},
+};
+
+use crate::{
+    defs::UNITS,
+    parse::{parse_with, ParseError},
+    prefix::PREFIXES,
+    quantity::{DimensionError, DynDimension, Quantity},
+};
+
+/// A unit and every name it goes by
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitEntry {
+    /// Used when formatting, e.g. `m`
+    pub symbol: String,
+    pub name: Option<String>,
+    pub plural: Option<String>,
+    /// Other spellings, e.g. `metre`
+    pub aliases: Vec<String>,
+    pub value: Quantity,
+}
+
+impl UnitEntry {
+    pub fn new(symbol: &str, value: Quantity) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            name: None,
+            plural: None,
+            aliases: Vec::new(),
+            value,
+        }
+    }
+
+    /// Sets the long name, and the plural to the name with an `s` if it isn't set yet
+    pub fn name(mut self, name: &str) -> Self {
+        self.plural.get_or_insert_with(|| format!("{}s", name));
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn plural(mut self, plural: &str) -> Self {
+        self.plural = Some(plural.to_string());
+        self
+    }
+
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.aliases.push(alias.to_string());
+        self
+    }
+
+    /// The symbol, name, plural and aliases
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(&self.symbol)
+            .chain(&self.name)
+            .chain(&self.plural)
+            .chain(&self.aliases)
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PrefixEntry {
+    symbol: String,
+    factor: f64,
+}
+
+/// One way of reading a word as a prefix and a unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interpretation<'a> {
+    /// The symbol and factor of the prefix
+    pub prefix: Option<(&'a str, f64)>,
+    /// `None` if the word is only a prefix, which is read as a plain number
+    pub unit: Option<&'a UnitEntry>,
+}
+
+impl Interpretation<'_> {
+    pub fn value(&self) -> Quantity {
+        let unit = self
+            .unit
+            .map_or(Quantity::new(1.0, DynDimension::NONE), |unit| unit.value);
+        unit * self.prefix.map_or(1.0, |(_, factor)| factor)
+    }
+
+    /// The prefix and unit symbols, e.g. `km`
+    pub fn symbol(&self) -> String {
+        let prefix = self.prefix.map_or("", |(symbol, _)| symbol);
+        let unit = self.unit.map_or("", |unit| unit.symbol.as_str());
+        format!("{}{}", prefix, unit)
+    }
+}
+
+/// Separates the prefix from the unit with a `-`, since `m-in` and `min` are different readings
+impl Display for Interpretation<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.prefix, self.unit) {
+            (Some((prefix, _)), Some(unit)) => write!(f, "{}-{}", prefix, unit.symbol),
+            (Some((prefix, _)), None) => write!(f, "{}-", prefix),
+            (None, Some(unit)) => write!(f, "{}", unit.symbol),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Why a unit couldn't be registered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The name is already taken by another unit
+    Duplicate { name: String, existing: String },
+    /// With the new unit, `name` could be read in more than one way, e.g. `min` as minutes and
+    /// as milli-inches
+    Ambiguous {
+        name: String,
+        interpretations: Vec<String>,
+    },
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::Duplicate { name, existing } => {
+                write!(f, "'{}' is already used by {}", name, existing)
+            }
+            RegistryError::Ambiguous {
+                name,
+                interpretations,
+            } => write!(
+                f,
+                "'{}' would be ambiguous: {}",
+                name,
+                interpretations.join(" or ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Maps unit symbols, names, plurals and aliases (`m`, `meter`, `meters`, `metre`) and prefixes
+/// to units, for parsing and formatting text at runtime.
+///
+/// `Quantity::from_str` uses the global registry (see `global`), which starts out as
+/// `UnitRegistry::builtin()`. Registries can also be filled from a GNU units file, see
+/// `UnitRegistry::load_units_dat`.
+#[derive(Debug, Clone, Default)]
+pub struct UnitRegistry {
+    entries: Vec<UnitEntry>,
+    /// Every name of every entry, to its index in `entries`
+    names: HashMap<String, usize>,
+    /// Ordered, so the first conflicting prefix `register` reports is always the same one
+    prefixes: BTreeMap<String, PrefixEntry>,
+}
+
+impl UnitRegistry {
+    /// A registry without any units or prefixes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The units of `defs::UNITS` and the prefixes of `prefix::PREFIXES` by symbol and lower
+    /// case name.
+    ///
+    /// These include readings that `register` would reject (`min` is also milli-inches, `ft`
+    /// femto-tonnes), `lookup` prefers the whole name for them.
+    pub fn builtin() -> Self {
+        let mut ret = Self::new();
+        for unit in UNITS {
+            let mut entry = UnitEntry::new(unit.symbol, unit.quantity())
+                .name(unit.name)
+                .plural(unit.plural);
+            for alias in unit.aliases {
+                entry = entry.alias(alias);
+            }
+            ret.register_shadowing(entry);
+        }
+        for (name, symbol, power) in PREFIXES {
+            let prefix = PrefixEntry {
+                symbol: symbol.to_string(),
+                factor: 10f64.powi(-power),
+            };
+            ret.prefixes.insert(name.to_lowercase(), prefix.clone());
+            ret.prefixes.insert(symbol.to_string(), prefix);
+        }
+        ret
+    }
+
+    /// Adds a unit, unless one of its names is taken or would make a word ambiguous
+    pub fn register(&mut self, entry: UnitEntry) -> Result<(), RegistryError> {
+        for name in entry.names() {
+            if let Some(&existing) = self.names.get(name) {
+                return Err(RegistryError::Duplicate {
+                    name: name.to_string(),
+                    existing: self.entries[existing].symbol.clone(),
+                });
+            }
+            // The new name might already be readable as prefix + unit
+            let existing = self.readings(name);
+            if !existing.is_empty() {
+                return Err(RegistryError::Ambiguous {
+                    name: name.to_string(),
+                    interpretations: std::iter::once(entry.symbol.clone())
+                        .chain(existing.iter().map(ToString::to_string))
+                        .collect(),
+                });
+            }
+            // or prefix + new name might already be a unit
+            for (prefix, PrefixEntry { symbol, .. }) in &self.prefixes {
+                if let Some(&existing) = self.names.get(&format!("{}{}", prefix, name)) {
+                    return Err(RegistryError::Ambiguous {
+                        name: format!("{}{}", prefix, name),
+                        interpretations: vec![
+                            self.entries[existing].symbol.clone(),
+                            format!("{}-{}", symbol, entry.symbol),
+                        ],
+                    });
+                }
+            }
+        }
+        self.register_shadowing(entry);
+        Ok(())
+    }
+
+    /// Adds a unit even if that makes some words ambiguous, taking over names that are already
+    /// in use. The whole names win over prefixed readings, as in `lookup`.
+    pub fn register_shadowing(&mut self, entry: UnitEntry) {
+        let index = self.entries.len();
+        for name in entry.names() {
+            self.names.insert(name.to_string(), index);
+        }
+        self.entries.push(entry);
+    }
+
+    /// Adds or replaces a unit that only has a symbol
+    pub fn define(&mut self, name: &str, value: Quantity) {
+        self.register_shadowing(UnitEntry::new(name, value));
+    }
+
+    /// Adds or replaces a prefix, which can be put in front of any unit to scale it by `factor`
+    pub fn define_prefix(&mut self, name: &str, factor: f64) {
+        let symbol = name.to_string();
+        self.prefixes
+            .insert(name.to_string(), PrefixEntry { symbol, factor });
+    }
+
+    /// The unit with this exact symbol, name, plural or alias
+    pub fn entry(&self, name: &str) -> Option<&UnitEntry> {
+        self.names.get(name).map(|&index| &self.entries[index])
+    }
+
+    /// Every way `word` can be read, best first: the whole word as a unit or a prefix, then
+    /// prefix + unit with the longest prefix first (so `dam` is a decameter before a
+    /// deci-am). If there are none, the same for `word` without a plural `s`.
+    pub fn interpretations(&self, word: &str) -> Vec<Interpretation<'_>> {
+        let ret = self.readings(word);
+        if !ret.is_empty() {
+            return ret;
+        }
+        match word.strip_suffix('s').filter(|s| !s.is_empty()) {
+            Some(singular) => self.readings(singular),
+            None => ret,
+        }
+    }
+
+    fn readings(&self, word: &str) -> Vec<Interpretation<'_>> {
+        let prefix = |name: &str| {
+            self.prefixes
+                .get(name)
+                .map(|prefix| (prefix.symbol.as_str(), prefix.factor))
+        };
+        let mut ret = Vec::new();
+        if let Some(unit) = self.entry(word) {
+            ret.push(Interpretation {
+                prefix: None,
+                unit: Some(unit),
+            });
+        }
+        if let Some(prefix) = prefix(word) {
+            ret.push(Interpretation {
+                prefix: Some(prefix),
+                unit: None,
+            });
+        }
+        for split in (1..word.len()).rev().filter(|&i| word.is_char_boundary(i)) {
+            if let (Some(prefix), Some(unit)) = (prefix(&word[..split]), self.entry(&word[split..]))
+            {
+                ret.push(Interpretation {
+                    prefix: Some(prefix),
+                    unit: Some(unit),
+                });
+            }
+        }
+        ret
+    }
+
+    /// Whether `word` can be read in more than one way
+    pub fn is_ambiguous(&self, word: &str) -> bool {
+        self.interpretations(word).len() > 1
+    }
+
+    /// Finds a unit by any of its names, with an optional prefix and an optional plural `s`.
+    ///
+    /// This is the first of `interpretations`: whole names win over prefixed ones, so `min` is
+    /// a minute rather than a milli-inch, and both win over plurals, so `ms` is a millisecond.
+    /// A prefix on its own is a plain number, `kilo` is 1000.
+    pub fn lookup(&self, word: &str) -> Option<Quantity> {
+        self.interpretations(word)
+            .first()
+            .map(Interpretation::value)
+    }
+
+    /// Parses an expression (see `Quantity::from_str`) with the units of this registry
+    pub fn parse(&self, input: &str) -> Result<Quantity, ParseError> {
+        parse_with(input, |name| self.lookup(name))
+    }
+
+    /// Formats `quantity` as a multiple of `unit`, see `canonical`
+    pub fn format_in(&self, quantity: &Quantity, unit: &str) -> Result<String, FormatError> {
+        let target = self.parse(unit).map_err(FormatError::Parse)?;
+        let value = quantity.value_in(&target).map_err(FormatError::Dimension)?;
+        Ok(format!("{} {}", value, self.canonical(unit)))
+    }
+
+    /// A single (optionally prefixed) unit written with its symbols, so `kilometers` gives
+    /// `km`. Other expressions are kept as they are.
+    pub fn canonical(&self, unit: &str) -> String {
+        let unit = unit.trim();
+        match self.interpretations(unit).first() {
+            Some(interpretation) if interpretation.unit.is_some() => interpretation.symbol(),
+            _ => unit.to_string(),
+        }
+    }
+}
+
+/// Why `UnitRegistry::format_in` failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatError {
+    Parse(ParseError),
+    Dimension(DimensionError),
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::Parse(err) => write!(f, "{}", err),
+            FormatError::Dimension(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+static GLOBAL: OnceLock<RwLock<UnitRegistry>> = OnceLock::new();
+
+fn global_lock() -> &'static RwLock<UnitRegistry> {
+    GLOBAL.get_or_init(|| RwLock::new(UnitRegistry::builtin()))
+}
+
+/// The registry `Quantity::from_str` resolves units with
+pub fn global() -> RwLockReadGuard<'static, UnitRegistry> {
+    global_lock().read().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Replaces the registry `Quantity::from_str` resolves units with
+pub fn set_global(registry: UnitRegistry) {
+    *global_lock().write().unwrap_or_else(|err| err.into_inner()) = registry;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookups() {
+        let registry = UnitRegistry::builtin();
+        assert_eq!(registry.lookup("km").unwrap().value(), 1e3);
+        assert_eq!(registry.lookup("kilometers").unwrap().value(), 1e3);
+        assert_eq!(registry.lookup("kilometres").unwrap().value(), 1e3);
+        assert_eq!(registry.lookup("metre"), registry.lookup("m"));
+        assert_eq!(registry.lookup("feet"), registry.lookup("ft"));
+        assert_eq!(
+            registry.lookup("kg").unwrap(),
+            Quantity::new(1.0, DynDimension::base(2))
+        );
+        assert_eq!(registry.lookup("min").unwrap().value(), 60.0);
+        assert_eq!(registry.lookup("us").unwrap().value(), 1e-6);
+        assert_eq!(registry.lookup("ms").unwrap().value(), 1e-3);
+        assert_eq!(registry.lookup("dam").unwrap().value(), 10.0);
+        // Kilotonnes, not knots
+        assert_eq!(registry.lookup("kt").unwrap().value(), 1e6);
+        assert_eq!(registry.lookup("kilo").unwrap().value(), 1e3);
+        assert_eq!(registry.lookup("xyz"), None);
+        for (name, symbol, power) in PREFIXES {
+            let scale = 10f64.powi(-power);
+            for word in [
+                format!("{}meters", name.to_lowercase()),
+                format!("{}m", symbol),
+            ] {
+                let value = registry.lookup(&word).map(|length| length.value());
+                assert!(
+                    value.is_some_and(|value| (value / scale - 1.0).abs() < 1e-12),
+                    "{}",
+                    word
+                );
+            }
+        }
+        assert_eq!(
+            registry.parse("1013 hectopascal").unwrap().value(),
+            101300.0
+        );
+
+        let readings: Vec<_> = registry
+            .interpretations("min")
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(readings, ["min", "m-in"]);
+        assert!(!registry.is_ambiguous("km"));
+
+        let mut registry = UnitRegistry::new();
+        registry.define("furlong", "201.168 m".parse().unwrap());
+        assert_eq!(registry.lookup("furlongs").unwrap().value(), 201.168);
+        assert_eq!(registry.lookup("km"), None);
+    }
+
+    #[test]
+    fn registration() {
+        let mut registry = UnitRegistry::builtin();
+        let smoot = UnitEntry::new("smoot", "1.7018 m".parse().unwrap()).name("smoot");
+        registry.register(smoot).unwrap();
+        assert!((registry.parse("2 ksmoots").unwrap().value() - 3403.6).abs() < 1e-9);
+        assert_eq!(registry.entry("smoots").unwrap().symbol, "smoot");
+
+        let err = registry
+            .register(UnitEntry::new("m", Quantity::new(1.0, DynDimension::NONE)))
+            .unwrap_err();
+        assert!(matches!(err, RegistryError::Duplicate { .. }));
+        // `mmin` is already milli-minutes
+        let err = registry
+            .register(UnitEntry::new(
+                "mmin",
+                Quantity::new(1.0, DynDimension::NONE),
+            ))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "'mmin' would be ambiguous: mmin or m-min");
+        // `min` would also be milli-inches
+        let mut minutes = UnitRegistry::new();
+        minutes.define_prefix("m", 1e-3);
+        minutes.define("min", "60 s".parse().unwrap());
+        let err = minutes
+            .register(UnitEntry::new("in", "2.54 cm".parse().unwrap()))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "'min' would be ambiguous: min or m-in");
+        // With several conflicts, the prefixes are checked in order
+        minutes.define_prefix("k", 1e3);
+        minutes.define("kx", "1 s".parse().unwrap());
+        minutes.define("mx", "1 s".parse().unwrap());
+        let err = minutes
+            .register(UnitEntry::new("x", "1 m".parse().unwrap()))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "'kx' would be ambiguous: kx or k-x");
+
+        let distance = "2.16e3 m".parse().unwrap();
+        assert_eq!(
+            registry.format_in(&distance, "kilometers").unwrap(),
+            "2.16 km"
+        );
+        assert_eq!(
+            registry.format_in(&distance, "km/s*s").unwrap(),
+            "2.16 km/s*s"
+        );
+        assert!(registry.format_in(&distance, "s").is_err());
+    }
+}
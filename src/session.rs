@@ -0,0 +1,197 @@
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+};
+
+use crate::{
+    parse::{parse_with, ParseError, ParseErrorKind},
+    quantity::Quantity,
+    registry,
+};
+
+/// The state of a calculator session: named quantities, and the lines that defined them.
+///
+/// Each line is `[name =] expression [-> unit]`. Names are looked up before units, so a
+/// variable can shadow a unit. Saving writes the defining lines, loading replays them.
+#[derive(Debug, Default, Clone)]
+pub struct Session {
+    variables: Vec<(String, Quantity)>,
+    history: Vec<String>,
+}
+
+/// The result of evaluating one line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluated {
+    /// The variable that was assigned, if any
+    pub name: Option<String>,
+    pub quantity: Quantity,
+    /// The value in the unit after `->`, and that unit's symbol (see `UnitRegistry::canonical`)
+    pub converted: Option<(f64, String)>,
+}
+
+impl Display for Evaluated {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "{} = ", name)?;
+        }
+        match &self.converted {
+            Some((value, unit)) => write!(f, "{} {}", value, unit),
+            None => write!(f, "{}", self.quantity),
+        }
+    }
+}
+
+/// An error in a line, with the byte offset into that line it refers to
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionError(pub ParseError);
+
+impl SessionError {
+    /// The offending line with a caret under the position of the error, followed by the error
+    pub fn render(&self, line: &str) -> String {
+        let column = line[..self.0.position.min(line.len())].chars().count();
+        format!("{}\n{}^\n{}", line, " ".repeat(column), self.0)
+    }
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<Quantity> {
+        self.variables
+            .iter()
+            .find(|(var, _)| var == name)
+            .map(|(_, value)| *value)
+    }
+
+    /// The variables in the order they were first assigned
+    pub fn variables(&self) -> impl Iterator<Item = (&str, Quantity)> {
+        self.variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+
+    pub fn eval(&mut self, line: &str) -> Result<Evaluated, SessionError> {
+        let (name, expr_start) = match line.split_once('=') {
+            Some((lhs, _)) if is_identifier(lhs.trim()) => (Some(lhs.trim()), lhs.len() + 1),
+            _ => (None, 0),
+        };
+        let (expr, target) = match line[expr_start..].split_once("->") {
+            Some((expr, target)) => (expr, Some((expr_start + expr.len(), target))),
+            None => (&line[expr_start..], None),
+        };
+
+        let quantity = self.parse(expr, expr_start)?;
+        let converted = match target {
+            Some((arrow, target)) => {
+                let unit = self.parse(target, arrow + 2)?;
+                let value = quantity.value_in(&unit).map_err(|err| {
+                    SessionError(ParseError {
+                        position: arrow,
+                        kind: ParseErrorKind::Dimension(Box::new(err)),
+                    })
+                })?;
+                Some((value, registry::global().canonical(target)))
+            }
+            None => None,
+        };
+
+        if let Some(name) = name {
+            match self.variables.iter_mut().find(|(var, _)| var == name) {
+                Some((_, value)) => *value = quantity,
+                None => self.variables.push((name.to_string(), quantity)),
+            }
+            self.history.push(line.trim().to_string());
+        }
+        Ok(Evaluated {
+            name: name.map(str::to_string),
+            quantity,
+            converted,
+        })
+    }
+
+    /// Writes every assignment made so far, one per line
+    pub fn save(&self, mut writer: impl Write) -> io::Result<()> {
+        for line in &self.history {
+            writeln!(writer, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Replays the lines written by `save`. Blank lines and lines starting with `#` are skipped,
+    /// errors carry the line number they were found on.
+    pub fn load(&mut self, reader: impl BufRead) -> io::Result<()> {
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            self.eval(&line).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {}", number + 1, err.render(&line)),
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    fn parse(&self, expr: &str, offset: usize) -> Result<Quantity, SessionError> {
+        let registry = registry::global();
+        parse_with(expr, |name| {
+            self.get(name).or_else(|| registry.lookup(name))
+        })
+        .map_err(|mut err| {
+            err.position += offset;
+            SessionError(err)
+        })
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variables() {
+        let mut session = Session::new();
+        session.eval("v = 12 m/s").unwrap();
+        session.eval("t = 3 min").unwrap();
+        let d = session.eval("d = v*t -> km").unwrap();
+        assert_eq!(d.converted, Some((2.16, "km".to_string())));
+        assert_eq!(d.to_string(), "d = 2.16 km");
+        assert_eq!(session.get("d").unwrap().value(), 2160.0);
+        assert_eq!(
+            session.eval("d -> kilometres").unwrap().to_string(),
+            "2.16 km"
+        );
+
+        let err = session.eval("v + t").unwrap_err();
+        assert_eq!(err.0.position, 2);
+        assert!(err.render("v + t").starts_with("v + t\n  ^\n"));
+        assert_eq!(session.eval("x = d -> s").unwrap_err().0.position, 6);
+        assert_eq!(session.get("x"), None);
+
+        let mut saved = Vec::new();
+        session.save(&mut saved).unwrap();
+        let mut loaded = Session::new();
+        loaded.load(&saved[..]).unwrap();
+        assert_eq!(loaded.get("d"), session.get("d"));
+        assert!(Session::new().load(&b"a = 1 m\nb = a + 1 s"[..]).is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! Systems of units, which pick one unit per base quantity and derive the unit of every other
+//! dimension from those: CGS measures a force in `g cm s^-2` (the dyne), Imperial in
+//! `lb ft s^-2` (the poundal).
+//!
+//! `Value::to_system` re-expresses a value in the coherent unit of a system, which is a regular
+//! `Unit` and converts back with `Value::convert`. The electromagnetic base quantity (current)
+//! keeps its SI unit in every system here, see `gaussian` for the CGS electromagnetic units.
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use typenum::{Integer, ToInt, Z0};
+
+use crate::{
+    base::Coherent,
+    dimension::Dimension,
+    quantity::{DynDimension, Quantity},
+    Unit, UnitValue, Value,
+};
+
+const fn dim(exponents: [i32; 7]) -> DynDimension {
+    DynDimension::from_ints(exponents)
+}
+
+/// A choice of unit for each base quantity
+pub trait UnitSystem: Copy {
+    /// The size of the unit of each base quantity in SI units, in the order of `dimension::Dim`
+    const BASE_UNITS: [f64; 7];
+    /// The symbols of those units
+    const SYMBOLS: [&'static str; 7];
+    /// Coherent units of the system with a name of their own, by their exponents
+    const NAMED: &'static [(DynDimension, &'static str)] = &[];
+
+    /// The size of the coherent unit of `dimension` in SI units
+    fn factor(dimension: &DynDimension) -> f64 {
+        Self::BASE_UNITS
+            .iter()
+            .zip(dimension.exponents())
+            .map(|(unit, (num, den))| match den {
+                1 => unit.powi(num),
+                _ => unit.powf(num as f64 / den as f64),
+            })
+            .product()
+    }
+
+    /// The symbol of the coherent unit of `dimension`, e.g. `dyn` or `cm s^-1`
+    fn symbol(dimension: &DynDimension) -> String {
+        if let Some((_, name)) = Self::NAMED.iter().find(|(named, _)| named == dimension) {
+            return name.to_string();
+        }
+        let parts: Vec<String> = Self::SYMBOLS
+            .iter()
+            .zip(dimension.exponents())
+            .filter(|(_, (num, _))| *num != 0)
+            .map(|(symbol, exp)| match exp {
+                (1, 1) => symbol.to_string(),
+                (num, 1) => format!("{}^{}", symbol, num),
+                (num, den) => format!("{}^{}/{}", symbol, num, den),
+            })
+            .collect();
+        if parts.is_empty() {
+            "1".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+/// The International System: metre, second, kilogram, mole, ampere, kelvin and candela
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Si;
+
+impl UnitSystem for Si {
+    const BASE_UNITS: [f64; 7] = [1.0; 7];
+    const SYMBOLS: [&'static str; 7] = crate::quantity::BASE_SYMBOLS;
+    const NAMED: &'static [(DynDimension, &'static str)] = &[
+        (dim([1, -2, 1, 0, 0, 0, 0]), "N"),
+        (dim([2, -2, 1, 0, 0, 0, 0]), "J"),
+        (dim([2, -3, 1, 0, 0, 0, 0]), "W"),
+        (dim([-1, -2, 1, 0, 0, 0, 0]), "Pa"),
+        (dim([0, -1, 0, 0, 0, 0, 0]), "Hz"),
+    ];
+}
+
+/// Centimetre, gram and second, with the SI units for the other base quantities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cgs;
+
+impl UnitSystem for Cgs {
+    const BASE_UNITS: [f64; 7] = [1e-2, 1.0, 1e-3, 1.0, 1.0, 1.0, 1.0];
+    const SYMBOLS: [&'static str; 7] = ["cm", "s", "g", "mol", "A", "K", "cd"];
+    const NAMED: &'static [(DynDimension, &'static str)] = &[
+        (dim([1, -2, 1, 0, 0, 0, 0]), "dyn"),
+        (dim([2, -2, 1, 0, 0, 0, 0]), "erg"),
+        (dim([-1, -2, 1, 0, 0, 0, 0]), "Ba"),
+        (dim([-1, -1, 1, 0, 0, 0, 0]), "P"),
+        (dim([2, -1, 0, 0, 0, 0, 0]), "St"),
+        (dim([1, -2, 0, 0, 0, 0, 0]), "Gal"),
+        (dim([0, -1, 0, 0, 0, 0, 0]), "Hz"),
+    ];
+}
+
+/// Foot, pound and second, with degrees Rankine for temperature and the SI units for the other
+/// base quantities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Imperial;
+
+impl UnitSystem for Imperial {
+    const BASE_UNITS: [f64; 7] = [0.3048, 1.0, 0.45359237, 1.0, 1.0, 5.0 / 9.0, 1.0];
+    const SYMBOLS: [&'static str; 7] = ["ft", "s", "lb", "mol", "A", "°R", "cd"];
+    const NAMED: &'static [(DynDimension, &'static str)] = &[(dim([1, -2, 1, 0, 0, 0, 0]), "pdl")];
+}
+
+/// The coherent unit of the dimension `D` in the system `S`, e.g. `SystemUnit<Cgs, Force>` is
+/// the dyne
+pub struct SystemUnit<S, D>(PhantomData<(S, D)>);
+
+impl<S, D> Clone for SystemUnit<S, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<S, D> Copy for SystemUnit<S, D> {}
+
+impl<S: UnitSystem, D: Dimension> Unit for SystemUnit<S, D> {
+    type Base = Coherent<D>;
+    type Dimension = D;
+    fn create() -> Self {
+        Self(PhantomData)
+    }
+    fn factor() -> f64 {
+        S::factor(&DynDimension::of::<D>())
+    }
+}
+
+impl<S: UnitSystem, D: Dimension> Debug for SystemUnit<S, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", S::symbol(&DynDimension::of::<D>()))
+    }
+}
+
+impl<V: UnitValue, P: Integer + ToInt<i32>, U: Unit, K> Value<V, P, U, K>
+where
+    U::Dimension: Dimension,
+{
+    /// Re-expresses this value in the coherent unit of the system `S`
+    pub fn to_system<S: UnitSystem>(&self) -> Value<V, Z0, SystemUnit<S, U::Dimension>, K> {
+        self.convert()
+    }
+
+    /// How many of the coherent unit of the system `S` this is
+    pub fn in_system<S: UnitSystem>(&self) -> V {
+        self.to_system::<S>().0
+    }
+}
+
+impl Quantity {
+    /// How many of the coherent unit of the system `S` this is
+    pub fn in_system<S: UnitSystem>(&self) -> f64 {
+        self.value() / S::factor(&self.dimension())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Gram, Meter, Second, Unitless},
+        close,
+        prefix::Kilo,
+    };
+
+    #[test]
+    fn systems() {
+        let per_s2 = Unitless::new(1f64) / (Second::new(1f64) * Second::new(1f64));
+        let force = Kilo::<Gram>::new(1f64) * Meter::new(1f64) * per_s2;
+        let dynes = force.to_system::<Cgs>();
+        assert!(close(*dynes.value(), 1e5, 1e-12));
+        assert_eq!(format!("{:?}", dynes), format!("{:?} dyn", dynes.value()));
+        assert!(close(
+            force.in_system::<Imperial>(),
+            7.233013851209894,
+            1e-12
+        ));
+        assert!(close(*dynes.to_system::<Si>().value(), 1.0, 1e-12));
+        assert!(dynes == force);
+
+        let speed = Meter::new(3f64) / Second::new(1f64);
+        let imperial = speed.to_system::<Imperial>();
+        assert!(format!("{:?}", imperial).ends_with(" ft s^-1"));
+        // Products leave the system, like any other product of values
+        assert!(close(*(imperial * imperial).value(), 9.0, 1e-12));
+        assert!(close(
+            *imperial.sqrt().to_base().value(),
+            3f64.sqrt(),
+            1e-12
+        ));
+
+        let energy: Quantity = "1 J".parse().unwrap();
+        assert!(close(energy.in_system::<Cgs>(), 1e7, 1e-12));
+        assert_eq!(Cgs::symbol(&energy.dimension()), "erg");
+        assert_eq!(Cgs::symbol(&DynDimension::NONE), "1");
+    }
+}
@@ -0,0 +1,708 @@
+//! The Unified Code for Units of Measure (<https://ucum.org/ucum>), case sensitive variant.
+//!
+//! Codes such as `mg/dL`, `mm[Hg]`, `10*3/uL` or `mL/min/{1.73_m2}` are parsed into a
+//! `UcumUnit`, which converts to and from `Quantity` and `Value`. The other direction,
+//! `Value::ucum_code` and `DynDimension::ucum_code`, writes the code of a unit.
+//!
+//! UCUM counts moles and equivalents as plain numbers (6.02...e23); here they have the
+//! `Amount` dimension as in the SI, so that `mmol/L` converts into an amount concentration.
+//! Arbitrary units (`[IU]`, `[arb'U]`) have no conversion and are rejected.
+
+use std::fmt::Display;
+
+use typenum::{Integer, ToInt};
+
+use crate::{
+    dimension::Dimension,
+    quantity::{DimensionError, DynDimension, Quantity},
+    Unit, Value,
+};
+
+/// A parsed UCUM code, as `si = value * scale + offset`. Only the special units `Cel` and
+/// `[degF]` have an offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UcumUnit {
+    pub scale: Quantity,
+    pub offset: f64,
+}
+
+impl UcumUnit {
+    /// `value` of this unit
+    pub fn to_quantity(&self, value: f64) -> Quantity {
+        Quantity::new(
+            value * self.scale.value() + self.offset,
+            self.scale.dimension(),
+        )
+    }
+
+    /// How many of this unit `quantity` is
+    pub fn from_quantity(&self, quantity: &Quantity) -> Result<f64, DimensionError> {
+        let unit = Quantity::new(1.0, self.scale.dimension());
+        let si = quantity.value_in(&unit)?;
+        Ok((si - self.offset) / self.scale.value())
+    }
+}
+
+/// What went wrong with a UCUM code
+#[derive(Debug, Clone, PartialEq)]
+pub enum UcumErrorKind {
+    UnexpectedEnd,
+    UnexpectedChar(char),
+    UnknownUnit(String),
+    /// A prefix in front of a unit that doesn't take one, like `k[in_i]`
+    NotMetric(String),
+    /// `[IU]` and friends have no definition in terms of other units
+    Arbitrary(String),
+    /// `Cel` and `[degF]` are offset from zero, so can't be multiplied, divided or raised
+    SpecialCombined(String),
+    /// An exponent of the dimension doesn't fit in an `i32`
+    ExponentOutOfRange,
+    Dimension(Box<DimensionError>),
+}
+
+/// A UCUM error and the byte offset into the code where it happened
+#[derive(Debug, Clone, PartialEq)]
+pub struct UcumError {
+    pub position: usize,
+    pub kind: UcumErrorKind,
+}
+
+impl Display for UcumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            UcumErrorKind::UnexpectedEnd => write!(f, "unexpected end of UCUM code"),
+            UcumErrorKind::UnexpectedChar(c) => {
+                write!(f, "unexpected '{}' at position {}", c, self.position)
+            }
+            UcumErrorKind::UnknownUnit(unit) => write!(f, "unknown UCUM unit '{}'", unit),
+            UcumErrorKind::NotMetric(unit) => write!(f, "'{}' can't take a prefix", unit),
+            UcumErrorKind::Arbitrary(unit) => {
+                write!(f, "arbitrary unit '{}' can't be converted", unit)
+            }
+            UcumErrorKind::SpecialCombined(unit) => {
+                write!(f, "special unit '{}' can't be combined with others", unit)
+            }
+            UcumErrorKind::ExponentOutOfRange => {
+                write!(f, "exponent out of range at position {}", self.position)
+            }
+            UcumErrorKind::Dimension(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for UcumError {}
+
+const fn dim(exponents: [i32; 7]) -> DynDimension {
+    DynDimension::from_ints(exponents)
+}
+
+enum Atom {
+    Linear(f64, DynDimension),
+    /// `si = value * scale + offset`
+    Special(f64, f64, DynDimension),
+    Arbitrary,
+}
+
+const NONE: DynDimension = DynDimension::NONE;
+const LENGTH: DynDimension = dim([1, 0, 0, 0, 0, 0, 0]);
+const TIME: DynDimension = dim([0, 1, 0, 0, 0, 0, 0]);
+const MASS: DynDimension = dim([0, 0, 1, 0, 0, 0, 0]);
+const AMOUNT: DynDimension = dim([0, 0, 0, 1, 0, 0, 0]);
+const TEMPATURE: DynDimension = dim([0, 0, 0, 0, 0, 1, 0]);
+const AREA: DynDimension = dim([2, 0, 0, 0, 0, 0, 0]);
+const VOLUME: DynDimension = dim([3, 0, 0, 0, 0, 0, 0]);
+const ENERGY: DynDimension = dim([2, -2, 1, 0, 0, 0, 0]);
+const PRESSURE: DynDimension = dim([-1, -2, 1, 0, 0, 0, 0]);
+const CATALYTIC: DynDimension = dim([0, -1, 0, 1, 0, 0, 0]);
+
+/// `(code, metric, atom)`. Only metric units take a prefix.
+const ATOMS: &[(&str, bool, Atom)] = &[
+    // Base units, UCUM's base for mass is the gram and for charge the coulomb
+    ("m", true, Atom::Linear(1.0, LENGTH)),
+    ("s", true, Atom::Linear(1.0, TIME)),
+    ("g", true, Atom::Linear(1e-3, MASS)),
+    ("rad", true, Atom::Linear(1.0, NONE)),
+    ("K", true, Atom::Linear(1.0, TEMPATURE)),
+    ("C", true, Atom::Linear(1.0, dim([0, 1, 0, 0, 1, 0, 0]))),
+    ("cd", true, Atom::Linear(1.0, dim([0, 0, 0, 0, 0, 0, 1]))),
+    // Dimensionless
+    ("10*", false, Atom::Linear(10.0, NONE)),
+    ("10^", false, Atom::Linear(10.0, NONE)),
+    ("[pi]", false, Atom::Linear(std::f64::consts::PI, NONE)),
+    ("%", false, Atom::Linear(1e-2, NONE)),
+    ("[ppth]", false, Atom::Linear(1e-3, NONE)),
+    ("[ppm]", false, Atom::Linear(1e-6, NONE)),
+    ("[ppb]", false, Atom::Linear(1e-9, NONE)),
+    ("bit", true, Atom::Linear(1.0, NONE)),
+    ("By", true, Atom::Linear(8.0, NONE)),
+    // SI units
+    ("mol", true, Atom::Linear(1.0, AMOUNT)),
+    ("sr", true, Atom::Linear(1.0, NONE)),
+    ("Hz", true, Atom::Linear(1.0, dim([0, -1, 0, 0, 0, 0, 0]))),
+    ("N", true, Atom::Linear(1.0, dim([1, -2, 1, 0, 0, 0, 0]))),
+    ("Pa", true, Atom::Linear(1.0, PRESSURE)),
+    ("J", true, Atom::Linear(1.0, ENERGY)),
+    ("W", true, Atom::Linear(1.0, dim([2, -3, 1, 0, 0, 0, 0]))),
+    ("A", true, Atom::Linear(1.0, dim([0, 0, 0, 0, 1, 0, 0]))),
+    ("V", true, Atom::Linear(1.0, dim([2, -3, 1, 0, -1, 0, 0]))),
+    ("F", true, Atom::Linear(1.0, dim([-2, 4, -1, 0, 2, 0, 0]))),
+    ("Ohm", true, Atom::Linear(1.0, dim([2, -3, 1, 0, -2, 0, 0]))),
+    ("S", true, Atom::Linear(1.0, dim([-2, 3, -1, 0, 2, 0, 0]))),
+    ("Wb", true, Atom::Linear(1.0, dim([2, -2, 1, 0, -1, 0, 0]))),
+    ("Cel", false, Atom::Special(1.0, 273.15, TEMPATURE)),
+    ("T", true, Atom::Linear(1.0, dim([0, -2, 1, 0, -1, 0, 0]))),
+    ("H", true, Atom::Linear(1.0, dim([2, -2, 1, 0, -2, 0, 0]))),
+    ("lm", true, Atom::Linear(1.0, dim([0, 0, 0, 0, 0, 0, 1]))),
+    ("lx", true, Atom::Linear(1.0, dim([-2, 0, 0, 0, 0, 0, 1]))),
+    ("Bq", true, Atom::Linear(1.0, dim([0, -1, 0, 0, 0, 0, 0]))),
+    ("Gy", true, Atom::Linear(1.0, dim([2, -2, 0, 0, 0, 0, 0]))),
+    ("Sv", true, Atom::Linear(1.0, dim([2, -2, 0, 0, 0, 0, 0]))),
+    ("kat", true, Atom::Linear(1.0, CATALYTIC)),
+    ("U", true, Atom::Linear(1e-6 / 60.0, CATALYTIC)),
+    ("eq", true, Atom::Linear(1.0, AMOUNT)),
+    ("osm", true, Atom::Linear(1.0, AMOUNT)),
+    // Accepted with the SI
+    ("min", false, Atom::Linear(60.0, TIME)),
+    ("h", false, Atom::Linear(3600.0, TIME)),
+    ("d", false, Atom::Linear(86400.0, TIME)),
+    ("wk", false, Atom::Linear(604800.0, TIME)),
+    ("a", false, Atom::Linear(365.25 * 86400.0, TIME)),
+    ("mo", false, Atom::Linear(365.25 * 86400.0 / 12.0, TIME)),
+    ("l", true, Atom::Linear(1e-3, VOLUME)),
+    ("L", true, Atom::Linear(1e-3, VOLUME)),
+    ("ar", true, Atom::Linear(100.0, AREA)),
+    ("t", true, Atom::Linear(1e3, MASS)),
+    ("bar", true, Atom::Linear(1e5, PRESSURE)),
+    ("u", true, Atom::Linear(1.66053906660e-27, MASS)),
+    ("eV", true, Atom::Linear(1.602176634e-19, ENERGY)),
+    ("atm", false, Atom::Linear(101325.0, PRESSURE)),
+    ("m[Hg]", true, Atom::Linear(133322.387415, PRESSURE)),
+    ("m[H2O]", true, Atom::Linear(9806.65, PRESSURE)),
+    ("cal", true, Atom::Linear(4.184, ENERGY)),
+    ("[Cal]", false, Atom::Linear(4184.0, ENERGY)),
+    // Customary units
+    ("[in_i]", false, Atom::Linear(0.0254, LENGTH)),
+    ("[ft_i]", false, Atom::Linear(0.3048, LENGTH)),
+    ("[yd_i]", false, Atom::Linear(0.9144, LENGTH)),
+    ("[mi_i]", false, Atom::Linear(1609.344, LENGTH)),
+    ("[nmi_i]", false, Atom::Linear(1852.0, LENGTH)),
+    (
+        "[kn_i]",
+        false,
+        Atom::Linear(1852.0 / 3600.0, dim([1, -1, 0, 0, 0, 0, 0])),
+    ),
+    ("[lb_av]", false, Atom::Linear(0.45359237, MASS)),
+    ("[oz_av]", false, Atom::Linear(0.028349523125, MASS)),
+    ("[gal_us]", false, Atom::Linear(3.785411784e-3, VOLUME)),
+    ("[psi]", false, Atom::Linear(6894.757293168361, PRESSURE)),
+    (
+        "[degF]",
+        false,
+        Atom::Special(5.0 / 9.0, 459.67 * 5.0 / 9.0, TEMPATURE),
+    ),
+    // Arbitrary units
+    ("[iU]", true, Atom::Arbitrary),
+    ("[IU]", true, Atom::Arbitrary),
+    ("[arb'U]", false, Atom::Arbitrary),
+];
+
+/// UCUM's decimal prefixes as `(code, power of ten)`
+const PREFIXES: &[(&str, i32)] = &[
+    ("Y", 24),
+    ("Z", 21),
+    ("E", 18),
+    ("P", 15),
+    ("T", 12),
+    ("G", 9),
+    ("M", 6),
+    ("k", 3),
+    ("h", 2),
+    ("da", 1),
+    ("d", -1),
+    ("c", -2),
+    ("m", -3),
+    ("u", -6),
+    ("n", -9),
+    ("p", -12),
+    ("f", -15),
+    ("a", -18),
+    ("z", -21),
+    ("y", -24),
+];
+
+/// UCUM's binary prefixes as `(code, factor)`
+const BINARY_PREFIXES: &[(&str, f64)] = &[
+    ("Ki", 1024.0),
+    ("Mi", 1048576.0),
+    ("Gi", 1073741824.0),
+    ("Ti", 1099511627776.0),
+];
+
+/// `10^power` rounded once, like the literal `1e{power}`, which `10f64.powi` isn't for the
+/// larger powers
+fn power_of_ten(power: i32) -> f64 {
+    format!("1e{}", power).parse().unwrap()
+}
+
+/// Parses a case sensitive UCUM code
+pub fn parse(code: &str) -> Result<UcumUnit, UcumError> {
+    let mut parser = Parser {
+        input: code,
+        position: 0,
+        special: None,
+    };
+    let scale = parser.main_term()?;
+    if let Some(c) = parser.peek() {
+        return Err(parser.error(UcumErrorKind::UnexpectedChar(c)));
+    }
+    match parser.special {
+        Some((name, scale, offset, dimension)) => {
+            if code.trim() != name {
+                return Err(UcumError {
+                    position: 0,
+                    kind: UcumErrorKind::SpecialCombined(name.to_string()),
+                });
+            }
+            Ok(UcumUnit {
+                scale: Quantity::new(scale, dimension),
+                offset,
+            })
+        }
+        None => Ok(UcumUnit { scale, offset: 0.0 }),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    special: Option<(&'static str, f64, f64, DynDimension)>,
+}
+
+impl Parser<'_> {
+    // mainTerm: '/' term | term
+    fn main_term(&mut self) -> Result<Quantity, UcumError> {
+        if self.peek() == Some('/') {
+            self.bump();
+            let result = self.term()?.checked_powi(-1);
+            self.in_range(result, 0)
+        } else {
+            self.term()
+        }
+    }
+
+    // term: component (('.' | '/') component)*
+    fn term(&mut self) -> Result<Quantity, UcumError> {
+        let mut ret = self.component()?;
+        loop {
+            let op = self.position;
+            let result = match self.peek() {
+                Some('.') => {
+                    self.bump();
+                    ret.checked_mul(self.component()?)
+                }
+                Some('/') => {
+                    self.bump();
+                    ret.checked_div(self.component()?)
+                }
+                _ => return Ok(ret),
+            };
+            ret = self.in_range(result, op)?;
+        }
+    }
+
+    // component: '(' term ')' | annotation | factor | simple unit with exponent and annotation
+    fn component(&mut self) -> Result<Quantity, UcumError> {
+        let ret = match self.peek() {
+            Some('(') => {
+                self.bump();
+                let ret = self.term()?;
+                match self.peek() {
+                    Some(')') => self.bump(),
+                    Some(c) => return Err(self.error(UcumErrorKind::UnexpectedChar(c))),
+                    None => return Err(self.error(UcumErrorKind::UnexpectedEnd)),
+                }
+                ret
+            }
+            Some('{') => Quantity::new(1.0, NONE),
+            Some(_) => self.annotatable()?,
+            None => return Err(self.error(UcumErrorKind::UnexpectedEnd)),
+        };
+        // Annotations don't change the unit
+        if self.peek() == Some('{') {
+            match self.input[self.position..].find('}') {
+                Some(end) => self.position += end + 1,
+                None => {
+                    self.position = self.input.len();
+                    return Err(self.error(UcumErrorKind::UnexpectedEnd));
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    fn annotatable(&mut self) -> Result<Quantity, UcumError> {
+        let start = self.position;
+        // A symbol runs up to an operator, with anything allowed inside square brackets
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            match c {
+                '[' => depth += 1,
+                ']' if depth > 0 => depth -= 1,
+                '.' | '/' | '(' | ')' | '{' if depth == 0 => break,
+                _ => {}
+            }
+            self.bump();
+        }
+        let text = &self.input[start..self.position];
+        if text.is_empty() {
+            return Err(self.error(UcumErrorKind::UnexpectedChar(self.peek().unwrap_or(' '))));
+        }
+        if text.bytes().all(|b| b.is_ascii_digit()) {
+            return text
+                .parse()
+                .map(|n| Quantity::new(n, NONE))
+                .map_err(|_| UcumError {
+                    position: start,
+                    kind: UcumErrorKind::UnknownUnit(text.to_string()),
+                });
+        }
+
+        // The exponent is a signed integer at the end, outside of any brackets. `10*3` is the
+        // unit `10*` cubed.
+        let mut split = text.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        if split < text.len() && text[..split].ends_with(['+', '-']) {
+            split -= 1;
+        }
+        let (symbol, exponent) = match text[split..].parse() {
+            Ok(exponent) => (&text[..split], exponent),
+            Err(_) => (text, 1),
+        };
+        let unit = self.simple_unit(symbol, start)?;
+        if exponent != 1 && self.special.is_some() {
+            return Err(UcumError {
+                position: start,
+                kind: UcumErrorKind::SpecialCombined(symbol.to_string()),
+            });
+        }
+        let result = unit.checked_powi(exponent);
+        self.in_range(result, start)
+    }
+
+    fn in_range(&self, result: Option<Quantity>, position: usize) -> Result<Quantity, UcumError> {
+        result.ok_or(UcumError {
+            position,
+            kind: UcumErrorKind::ExponentOutOfRange,
+        })
+    }
+
+    fn simple_unit(&mut self, symbol: &str, start: usize) -> Result<Quantity, UcumError> {
+        let error = |kind| UcumError {
+            position: start,
+            kind,
+        };
+        let (prefix, atom) = match find_atom(symbol) {
+            Some(atom) => (1.0, atom),
+            None => PREFIXES
+                .iter()
+                .map(|&(prefix, power)| (prefix, power_of_ten(power)))
+                .chain(BINARY_PREFIXES.iter().copied())
+                .filter_map(|(prefix, factor)| {
+                    Some((factor, find_atom(symbol.strip_prefix(prefix)?)?))
+                })
+                .max_by_key(|(_, (code, _, _))| code.len())
+                .ok_or_else(|| error(UcumErrorKind::UnknownUnit(symbol.to_string())))?,
+        };
+        let (code, metric, atom) = atom;
+        if prefix != 1.0 && !metric {
+            return Err(error(UcumErrorKind::NotMetric(code.to_string())));
+        }
+        match *atom {
+            Atom::Linear(factor, dimension) => Ok(Quantity::new(prefix * factor, dimension)),
+            Atom::Special(scale, offset, dimension) => {
+                self.special = Some((code, scale, offset, dimension));
+                Ok(Quantity::new(scale, dimension))
+            }
+            Atom::Arbitrary => Err(error(UcumErrorKind::Arbitrary(symbol.to_string()))),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn error(&self, kind: UcumErrorKind) -> UcumError {
+        UcumError {
+            position: self.position,
+            kind,
+        }
+    }
+}
+
+fn find_atom(symbol: &str) -> Option<&'static (&'static str, bool, Atom)> {
+    ATOMS.iter().find(|(code, _, _)| *code == symbol)
+}
+
+/// The code of each base quantity's coherent unit, in the order of `dimension::Dim`
+const BASE_CODES: [&str; 7] = ["m", "s", "kg", "mol", "A", "K", "cd"];
+
+impl DynDimension {
+    /// The UCUM code of the coherent SI unit of this dimension, e.g. `m.s-2`. `None` for
+    /// fractional exponents, which UCUM can't express.
+    pub fn ucum_code(&self) -> Option<String> {
+        ucum_code(self, 0)
+    }
+}
+
+/// The code of `10^exp10` coherent units of `dimension`. A single unit gets a prefix if there
+/// is one for the power of ten (`km`, `cm2`, `g`), anything else a `10*` factor.
+fn ucum_code(dimension: &DynDimension, exp10: i32) -> Option<String> {
+    let exponents = dimension.exponents();
+    if exponents.iter().any(|&(_, den)| den != 1) {
+        return None;
+    }
+    let parts: Vec<(usize, i32)> = exponents
+        .iter()
+        .enumerate()
+        .filter(|(_, (num, _))| *num != 0)
+        .map(|(i, (num, _))| (i, *num))
+        .collect();
+    let exponent = |n: i32| if n == 1 { String::new() } else { n.to_string() };
+
+    if let [(i, n)] = parts[..] {
+        // Prefixes go on the gram rather than the kilogram
+        let (atom, exp10) = if i == 2 {
+            ("g", exp10 + 3 * n)
+        } else {
+            (BASE_CODES[i], exp10)
+        };
+        if exp10 % n == 0 {
+            let prefix = match exp10 / n {
+                0 => Some(""),
+                power => PREFIXES
+                    .iter()
+                    .find(|(_, p)| *p == power)
+                    .map(|(code, _)| *code),
+            };
+            if let Some(prefix) = prefix {
+                return Some(format!("{}{}{}", prefix, atom, exponent(n)));
+            }
+        }
+    }
+
+    let units = parts
+        .iter()
+        .map(|&(i, n)| format!("{}{}", BASE_CODES[i], exponent(n)));
+    let code: Vec<String> = match exp10 {
+        0 => units.collect(),
+        exp10 => std::iter::once(format!("10*{}", exp10))
+            .chain(units)
+            .collect(),
+    };
+    Some(if code.is_empty() {
+        "1".to_string()
+    } else {
+        code.join(".")
+    })
+}
+
+impl Quantity {
+    /// How many of the UCUM unit `code` this is
+    pub fn to_ucum(&self, code: &str) -> Result<f64, UcumError> {
+        parse(code)?.from_quantity(self).map_err(|err| UcumError {
+            position: 0,
+            kind: UcumErrorKind::Dimension(Box::new(err)),
+        })
+    }
+
+    /// `value` of the UCUM unit `code`
+    pub fn from_ucum(value: f64, code: &str) -> Result<Self, UcumError> {
+        Ok(parse(code)?.to_quantity(value))
+    }
+}
+
+impl<P: Integer + ToInt<i32>, U: Unit, K> Value<f64, P, U, K>
+where
+    U::Dimension: Dimension,
+{
+    /// The UCUM code of this value's unit, e.g. `km` for `Kilo<Meter>` or `m.s-1` for a speed.
+    /// `None` if the unit has fractional exponents or isn't a power of ten of the SI unit.
+    pub fn ucum_code(&self) -> Option<String> {
+        if U::factor() != 1.0 {
+            return None;
+        }
+        ucum_code(
+            &DynDimension::of::<U::Dimension>(),
+            -(P::to_i32() + U::SCALE),
+        )
+    }
+
+    /// How many of the UCUM unit `code` this is
+    pub fn to_ucum(&self, code: &str) -> Result<f64, UcumError> {
+        Quantity::from(Self::new(*self.value())).to_ucum(code)
+    }
+
+    /// `value` of the UCUM unit `code`, which must have the dimension of `U`
+    pub fn from_ucum(value: f64, code: &str) -> Result<Self, UcumError> {
+        Quantity::from_ucum(value, code)?
+            .to_value()
+            .map_err(|err| UcumError {
+                position: 0,
+                kind: UcumErrorKind::Dimension(Box::new(err)),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Coherent, Gram, Meter, Second, Unitless},
+        close, dimension,
+        prefix::{Centi, Kilo, Milli, Yocto, Yotta},
+    };
+    use typenum::{P2, Z0};
+
+    /// Examples from the UCUM specification and its conformance tests, with the size of one
+    /// unit in coherent SI units and its dimension
+    #[test]
+    fn conformance() {
+        let cases: &[(&str, f64, [i32; 7])] = &[
+            ("m", 1.0, [1, 0, 0, 0, 0, 0, 0]),
+            ("g", 1e-3, [0, 0, 1, 0, 0, 0, 0]),
+            ("kg", 1.0, [0, 0, 1, 0, 0, 0, 0]),
+            ("mg/dL", 1e-2, [-3, 0, 1, 0, 0, 0, 0]),
+            ("mg/dl", 1e-2, [-3, 0, 1, 0, 0, 0, 0]),
+            ("mm[Hg]", 133.322387415, [-1, -2, 1, 0, 0, 0, 0]),
+            ("cm[H2O]", 98.0665, [-1, -2, 1, 0, 0, 0, 0]),
+            ("10*3/uL", 1e12, [-3, 0, 0, 0, 0, 0, 0]),
+            ("10^3/uL", 1e12, [-3, 0, 0, 0, 0, 0, 0]),
+            ("10*-7", 1e-7, [0; 7]),
+            ("mL/min/{1.73_m2}", 1e-6 / 60.0, [3, -1, 0, 0, 0, 0, 0]),
+            ("kg.m/s2", 1.0, [1, -2, 1, 0, 0, 0, 0]),
+            ("kg/m2", 1.0, [-2, 0, 1, 0, 0, 0, 0]),
+            ("/min", 1.0 / 60.0, [0, -1, 0, 0, 0, 0, 0]),
+            ("s-1", 1.0, [0, -1, 0, 0, 0, 0, 0]),
+            ("{rbc}", 1.0, [0; 7]),
+            ("%", 1e-2, [0; 7]),
+            ("[ppm]", 1e-6, [0; 7]),
+            ("mmol/L", 1.0, [-3, 0, 0, 1, 0, 0, 0]),
+            ("meq/L", 1.0, [-3, 0, 0, 1, 0, 0, 0]),
+            ("U/L", 1e-3 / 60.0, [-3, -1, 0, 1, 0, 0, 0]),
+            ("umol/min", 1e-6 / 60.0, [0, -1, 0, 1, 0, 0, 0]),
+            (
+                "4.[pi].10*-7.N/A2",
+                1.2566370614359173e-6,
+                [1, -2, 1, 0, -2, 0, 0],
+            ),
+            ("[in_i]", 0.0254, [1, 0, 0, 0, 0, 0, 0]),
+            ("[ft_i]2", 0.09290304, [2, 0, 0, 0, 0, 0, 0]),
+            ("[lb_av]", 0.45359237, [0, 0, 1, 0, 0, 0, 0]),
+            ("[mi_i]/h", 0.44704, [1, -1, 0, 0, 0, 0, 0]),
+            ("kPa", 1e3, [-1, -2, 1, 0, 0, 0, 0]),
+            ("daL", 1e-2, [3, 0, 0, 0, 0, 0, 0]),
+            ("Ym", 1e24, [1, 0, 0, 0, 0, 0, 0]),
+            ("ym", 1e-24, [1, 0, 0, 0, 0, 0, 0]),
+            ("(m.s)/(kg)", 1.0, [1, 1, -1, 0, 0, 0, 0]),
+            ("mV.ms", 1e-6, [2, -2, 1, 0, -1, 0, 0]),
+            ("KiBy", 8192.0, [0; 7]),
+            ("Cel", 1.0, [0, 0, 0, 0, 0, 1, 0]),
+        ];
+        for &(code, size, dimension) in cases {
+            let unit = parse(code).unwrap_or_else(|err| panic!("{}: {}", code, err));
+            assert!(
+                close(unit.scale.value(), size, 1e-12),
+                "{}: {:?}",
+                code,
+                unit
+            );
+            assert_eq!(
+                unit.scale.dimension(),
+                DynDimension::from_ints(dimension),
+                "{}",
+                code
+            );
+        }
+
+        let cases: &[(&str, UcumErrorKind)] = &[
+            ("k[in_i]", UcumErrorKind::NotMetric("[in_i]".to_string())),
+            ("kmin", UcumErrorKind::NotMetric("min".to_string())),
+            ("[IU]/L", UcumErrorKind::Arbitrary("[IU]".to_string())),
+            ("Cel/s", UcumErrorKind::SpecialCombined("Cel".to_string())),
+            ("Cel2", UcumErrorKind::SpecialCombined("Cel".to_string())),
+            ("MG", UcumErrorKind::UnknownUnit("MG".to_string())),
+            ("m/", UcumErrorKind::UnexpectedEnd),
+            ("(m", UcumErrorKind::UnexpectedEnd),
+            ("mL{", UcumErrorKind::UnexpectedEnd),
+            ("m..s", UcumErrorKind::UnexpectedChar('.')),
+            ("m)", UcumErrorKind::UnexpectedChar(')')),
+            ("m2147483647.m", UcumErrorKind::ExponentOutOfRange),
+        ];
+        for (code, kind) in cases {
+            assert_eq!(&parse(code).unwrap_err().kind, kind, "{}", code);
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        let glucose = Quantity::from_ucum(100.0, "mg/dL").unwrap();
+        assert!(close(glucose.to_ucum("g/L").unwrap(), 1.0, 1e-12));
+        assert!(close(
+            Quantity::from_ucum(37.0, "Cel").unwrap().value(),
+            310.15,
+            1e-12
+        ));
+        assert!(close(
+            Quantity::from_ucum(98.6, "[degF]")
+                .unwrap()
+                .to_ucum("Cel")
+                .unwrap(),
+            37.0,
+            1e-12
+        ));
+        assert!(glucose.to_ucum("mm[Hg]").is_err());
+
+        let height = Value::<f64, Z0, Meter>::from_ucum(72.0, "[in_i]").unwrap();
+        assert!(close(*height.value(), 1.8288, 1e-12));
+        assert!(close(
+            Kilo::<Meter>::new(1f64).to_ucum("[mi_i]").unwrap(),
+            0.621371192237334,
+            1e-12
+        ));
+        assert!(Value::<f64, Z0, Second>::from_ucum(1.0, "m").is_err());
+
+        assert_eq!(Kilo::<Meter>::new(1f64).ucum_code().unwrap(), "km");
+        assert_eq!(Gram::new(1f64).ucum_code().unwrap(), "g");
+        assert_eq!(Kilo::<Gram>::new(1f64).ucum_code().unwrap(), "kg");
+        assert_eq!(Milli::<Gram>::new(1f64).ucum_code().unwrap(), "mg");
+        assert_eq!(Yotta::<Meter>::new(1f64).ucum_code().unwrap(), "Ym");
+        assert_eq!(Yocto::<Meter>::new(1f64).ucum_code().unwrap(), "ym");
+        assert_eq!(Yocto::<Gram>::new(1f64).ucum_code().unwrap(), "yg");
+        assert_eq!(
+            Centi::<Meter>::new(1f64).powi::<P2>().ucum_code().unwrap(),
+            "cm2"
+        );
+        assert_eq!(Unitless::new(1f64).ucum_code().unwrap(), "1");
+        assert_eq!(Kilo::<Unitless>::new(1f64).ucum_code().unwrap(), "10*3");
+        assert_eq!(
+            Value::<f64, Z0, Coherent<dimension::Force>>::new(1.0)
+                .ucum_code()
+                .unwrap(),
+            "m.s-2.kg"
+        );
+        assert_eq!(
+            (Unitless::new(1f64) / Second::new(1f64)).sqrt().ucum_code(),
+            None
+        );
+        let code = (Kilo::<Meter>::new(1f64) / Second::new(1f64))
+            .ucum_code()
+            .unwrap();
+        assert_eq!(code, "10*3.m.s-1");
+        assert!(close(parse(&code).unwrap().scale.value(), 1e3, 1e-12));
+    }
+}
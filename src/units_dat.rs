@@ -0,0 +1,580 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    parse::parse_with,
+    quantity::{DynDimension, Quantity, BASE_SYMBOLS},
+    registry::UnitRegistry,
+};
+
+/// A definition in a units file that couldn't be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadError {
+    /// The file the definition is in, `None` for text passed to `load_units_dat`
+    pub file: Option<PathBuf>,
+    /// 1-based, the first line of the definition if it was continued with `\`
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// The long names GNU units gives the primitive units, next to `BASE_SYMBOLS`
+const BASE_NAMES: [&str; 7] = [
+    "meter", "second", "kilogram", "mole", "ampere", "kelvin", "candela",
+];
+
+/// How deeply `!include`s may nest, in case the files don't form a cycle but are generated
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+struct Definition {
+    file: Option<PathBuf>,
+    line: usize,
+    name: String,
+    expr: String,
+    prefix: bool,
+}
+
+#[derive(Default)]
+struct Loader {
+    definitions: Vec<Definition>,
+    errors: Vec<LoadError>,
+    primitives: Vec<(String, Quantity)>,
+    variables: HashMap<String, String>,
+    /// The canonical paths of the files being read, outermost first
+    including: Vec<PathBuf>,
+}
+
+impl UnitRegistry {
+    /// Loads definitions in the format of GNU units' `definitions.units`:
+    ///
+    /// ```text
+    /// m           !               # a primitive unit, must be one of the SI base units
+    /// radian      !dimensionless
+    /// kilo-       1e3             # a prefix
+    /// k-          kilo
+    /// ft          12 inch         # definitions may refer to ones further down
+    /// inch        2.54 cm
+    /// ```
+    ///
+    /// Lines may be continued with a trailing `\`, and `#` starts a comment. Of the `!`
+    /// directives, `!locale`, `!var`, `!varnot` and `!set` select blocks as GNU units does
+    /// (`!locale en_US` is the active locale and `!var` checks the environment), `!include`
+    /// is an error since there is no file to resolve it against (see `load_units_file`) and
+    /// the ones which only affect the interactive program are ignored.
+    ///
+    /// Everything that can be resolved is added to the registry, even if other definitions
+    /// fail. Nonlinear units (`tempF(x)`) and tables (`wiregauge[in]`) aren't supported.
+    pub fn load_units_dat(&mut self, source: &str) -> Result<(), Vec<LoadError>> {
+        let mut loader = Loader::default();
+        loader.read(source, None);
+        loader.resolve(self)
+    }
+
+    /// Like `load_units_dat`, with `!include`s resolved relative to the including file
+    pub fn load_units_file(&mut self, path: impl AsRef<Path>) -> Result<(), Vec<LoadError>> {
+        let mut loader = Loader::default();
+        loader.read_file(path.as_ref(), None, 0);
+        loader.resolve(self)
+    }
+}
+
+impl Loader {
+    fn read_file(&mut self, path: &Path, from: Option<&Path>, line: usize) {
+        let source = fs::canonicalize(path)
+            .and_then(|canonical| fs::read_to_string(path).map(|source| (canonical, source)));
+        match source {
+            Ok((canonical, _)) if self.including.contains(&canonical) => self.error(
+                from,
+                line,
+                format!("circular !include of {}", path.display()),
+            ),
+            Ok(_) if self.including.len() >= MAX_INCLUDE_DEPTH => self.error(
+                from,
+                line,
+                format!("!include of {} is nested too deeply", path.display()),
+            ),
+            Ok((canonical, source)) => {
+                self.including.push(canonical);
+                self.read(&source, Some(path));
+                self.including.pop();
+            }
+            Err(err) => self.error(
+                from,
+                line,
+                format!("can't read {}: {}", path.display(), err),
+            ),
+        }
+    }
+
+    /// Collects the definitions of `source`, in file order
+    fn read(&mut self, source: &str, file: Option<&Path>) {
+        // Whether each enclosing `!locale` / `!var` block is active
+        let mut blocks: Vec<bool> = Vec::new();
+        let mut lines = source.lines().enumerate();
+        while let Some((index, text)) = lines.next() {
+            let number = index + 1;
+            let mut text = text.to_string();
+            while text.ends_with('\\') {
+                text.pop();
+                match lines.next() {
+                    Some((_, next)) => text.push_str(next),
+                    None => break,
+                }
+            }
+            let text = text.split('#').next().unwrap_or_default().trim();
+            let active = blocks.iter().all(|&b| b);
+
+            if let Some(directive) = text.strip_prefix('!') {
+                let mut words = directive.split_whitespace();
+                let command = words.next().unwrap_or_default();
+                let args: Vec<&str> = words.collect();
+                match command {
+                    "locale" => blocks.push(args.first() == Some(&"en_US")),
+                    "var" | "varnot" => {
+                        let value = args.first().and_then(|name| {
+                            self.variables
+                                .get(*name)
+                                .cloned()
+                                .or_else(|| std::env::var(name).ok())
+                        });
+                        let matches =
+                            value.is_some_and(|value| args[1..].contains(&value.as_str()));
+                        blocks.push(matches == (command == "var"));
+                    }
+                    "endlocale" | "endvar" => {
+                        if blocks.pop().is_none() {
+                            self.error(
+                                file,
+                                number,
+                                format!("!{} without a matching block", command),
+                            );
+                        }
+                    }
+                    "set" if active => match args[..] {
+                        [name, value] => {
+                            self.variables.entry(name.to_string()).or_insert_with(|| {
+                                std::env::var(name).unwrap_or_else(|_| value.to_string())
+                            });
+                        }
+                        _ => self.error(file, number, "!set needs a name and a value".to_string()),
+                    },
+                    "include" if active => match (args.first(), file) {
+                        (Some(include), Some(file)) => {
+                            let path = file.parent().unwrap_or(Path::new("")).join(include);
+                            self.read_file(&path, Some(file), number);
+                        }
+                        (Some(_), None) => self.error(
+                            file,
+                            number,
+                            "!include needs a file to be relative to".to_string(),
+                        ),
+                        (None, _) => {
+                            self.error(file, number, "!include needs a file name".to_string())
+                        }
+                    },
+                    "utf8" | "endutf8" | "message" | "prompt" | "unitlist" | "set" | "include" => {}
+                    _ => self.error(file, number, format!("unknown directive !{}", command)),
+                }
+                continue;
+            }
+            if text.is_empty() || !active {
+                continue;
+            }
+
+            let (name, expr) = text
+                .split_once(char::is_whitespace)
+                .map(|(name, expr)| (name, expr.trim()))
+                .unwrap_or((text, ""));
+            if name.contains(['(', '[']) {
+                self.error(
+                    file,
+                    number,
+                    format!("nonlinear unit '{}' is not supported", name),
+                );
+            } else if expr.is_empty() {
+                self.error(file, number, format!("'{}' has no definition", name));
+            } else if expr == "!" {
+                match BASE_SYMBOLS
+                    .iter()
+                    .zip(BASE_NAMES)
+                    .position(|(symbol, long)| name == *symbol || name == long)
+                {
+                    Some(i) => self
+                        .primitives
+                        .push((name.to_string(), Quantity::new(1.0, DynDimension::base(i)))),
+                    None => self.error(
+                        file,
+                        number,
+                        format!("primitive unit '{}' is not one of the SI base units", name),
+                    ),
+                }
+            } else if expr == "!dimensionless" {
+                self.primitives
+                    .push((name.to_string(), Quantity::new(1.0, DynDimension::NONE)));
+            } else {
+                let (name, prefix) = match name.strip_suffix('-') {
+                    Some(name) => (name, true),
+                    None => (name, false),
+                };
+                self.definitions.push(Definition {
+                    file: file.map(Path::to_path_buf),
+                    line: number,
+                    name: name.to_string(),
+                    expr: expr.to_string(),
+                    prefix,
+                });
+            }
+        }
+        if !blocks.is_empty() {
+            self.error(
+                file,
+                source.lines().count(),
+                "unterminated !locale or !var block".to_string(),
+            );
+        }
+    }
+
+    /// Adds the collected definitions to `registry`. Every name is known before anything is
+    /// resolved, so a word that is defined further down is read as that definition rather than
+    /// as a prefix and a unit that happen to be defined already.
+    fn resolve(mut self, registry: &mut UnitRegistry) -> Result<(), Vec<LoadError>> {
+        for (name, value) in &self.primitives {
+            registry.define(name, *value);
+        }
+        let mut resolver = Resolver {
+            registry,
+            states: vec![State::Pending; self.definitions.len()],
+            units: HashMap::new(),
+            prefixes: HashMap::new(),
+            definitions: self.definitions,
+            errors: Vec::new(),
+        };
+        for (index, definition) in resolver.definitions.iter().enumerate() {
+            let names = if definition.prefix {
+                &mut resolver.prefixes
+            } else {
+                &mut resolver.units
+            };
+            names
+                .entry(definition.name.clone())
+                .or_insert_with(Vec::new)
+                .push(index);
+        }
+        for index in 0..resolver.definitions.len() {
+            resolver.definition(index);
+        }
+        self.errors.append(&mut resolver.errors);
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            self.errors
+                .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+            Err(self.errors)
+        }
+    }
+
+    fn error(&mut self, file: Option<&Path>, line: usize, message: String) {
+        self.errors.push(LoadError {
+            file: file.map(Path::to_path_buf),
+            line,
+            message,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Pending,
+    /// On the stack of definitions being resolved, so referring to it is a cycle
+    Resolving,
+    Done,
+    Failed,
+}
+
+/// Resolves definitions on demand, the ones a definition refers to first
+struct Resolver<'a> {
+    registry: &'a mut UnitRegistry,
+    definitions: Vec<Definition>,
+    states: Vec<State>,
+    /// The definitions of every unit and prefix name, in file order
+    units: HashMap<String, Vec<usize>>,
+    prefixes: HashMap<String, Vec<usize>>,
+    errors: Vec<LoadError>,
+}
+
+impl Resolver<'_> {
+    /// Resolves the definition at `index` after the ones it refers to. Uses a work stack rather
+    /// than recursion, so long chains of forward references can't overflow the call stack.
+    fn definition(&mut self, index: usize) {
+        let mut stack = vec![index];
+        while let Some(&top) = stack.last() {
+            match self.states[top] {
+                State::Done | State::Failed => {
+                    stack.pop();
+                    continue;
+                }
+                State::Pending => self.states[top] = State::Resolving,
+                State::Resolving => {}
+            }
+            let pending: Vec<usize> = self
+                .dependencies(&self.definitions[top].expr)
+                .into_iter()
+                .flat_map(|(_, indices, _)| indices)
+                .filter(|&index| self.states[index] == State::Pending)
+                .collect();
+            if pending.is_empty() {
+                self.resolve(top);
+                stack.pop();
+            } else {
+                // Popped in file order, so the last definition of a name is the one that counts
+                stack.extend(pending.into_iter().rev());
+            }
+        }
+    }
+
+    /// Adds the definition at `index` to the registry, once nothing it refers to is pending
+    fn resolve(&mut self, index: usize) {
+        let definition = &self.definitions[index];
+        let (name, expr, prefix) = (
+            definition.name.clone(),
+            definition.expr.clone(),
+            definition.prefix,
+        );
+
+        let result = self
+            .check(&expr)
+            .and_then(|()| self.registry.parse(&expr).map_err(|err| err.to_string()))
+            .map_err(|err| format!("can't resolve '{}': {}", name, err));
+        let result = result.and_then(|value| match prefix {
+            true if !value.dimension().is_dimensionless() => {
+                Err(format!("prefix '{}-' must be a plain number", name))
+            }
+            true => {
+                self.registry.define_prefix(&name, value.value());
+                Ok(())
+            }
+            false => {
+                self.registry.define(&name, value);
+                Ok(())
+            }
+        });
+        self.states[index] = match result {
+            Ok(()) => State::Done,
+            Err(message) => {
+                let definition = &self.definitions[index];
+                self.errors.push(LoadError {
+                    file: definition.file.clone(),
+                    line: definition.line,
+                    message,
+                });
+                State::Failed
+            }
+        };
+    }
+
+    /// Fails if a word of `expr` is the whole name of a definition that is part of a cycle or
+    /// couldn't be resolved, instead of letting it be read as a prefix and a unit
+    fn check(&self, expr: &str) -> Result<(), String> {
+        let state = |names: &HashMap<String, Vec<usize>>, word: &str| {
+            names
+                .get(word)
+                .and_then(|indices| indices.last())
+                .map(|&index| self.states[index])
+        };
+        for (word, _, whole) in self.dependencies(expr) {
+            if !whole {
+                continue;
+            }
+            let states = [state(&self.units, &word), state(&self.prefixes, &word)];
+            if states.contains(&Some(State::Resolving)) {
+                return Err(format!("circular definition of '{}'", word));
+            }
+            if states.contains(&Some(State::Failed)) {
+                return Err(format!("'{}' couldn't be resolved", word));
+            }
+        }
+        Ok(())
+    }
+
+    /// The definitions the words of `expr` may refer to, as `(name, definitions, whole)`. If a
+    /// word is the whole name of a definition only that one counts, otherwise any other reading
+    /// the registry might pick: a plural, or a prefix and a unit.
+    fn dependencies(&self, expr: &str) -> Vec<(String, Vec<usize>, bool)> {
+        let words = RefCell::new(Vec::new());
+        // Every word is a plain number here, only the names matter
+        let _ = parse_with(expr, |word| {
+            words.borrow_mut().push(word.to_string());
+            Some(Quantity::new(1.0, DynDimension::NONE))
+        });
+        let mut ret = Vec::new();
+        let mut add = |names: &HashMap<String, Vec<usize>>, name: &str, whole: bool| {
+            if let Some(indices) = names.get(name) {
+                ret.push((name.to_string(), indices.clone(), whole));
+            }
+        };
+        for word in words.into_inner() {
+            if self.units.contains_key(&word) || self.prefixes.contains_key(&word) {
+                add(&self.units, &word, true);
+                add(&self.prefixes, &word, true);
+                continue;
+            }
+            let singular = word.strip_suffix('s').filter(|s| !s.is_empty());
+            for word in std::iter::once(word.as_str()).chain(singular) {
+                add(&self.units, word, false);
+                for split in (1..word.len()).filter(|&i| word.is_char_boundary(i)) {
+                    let (prefix, unit) = word.split_at(split);
+                    if self.units.contains_key(unit) || self.registry.entry(unit).is_some() {
+                        add(&self.prefixes, prefix, false);
+                    }
+                    add(&self.units, unit, false);
+                }
+            }
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNITS: &str = r"
+# A small part of definitions.units
+m           !
+s           !
+kg          !
+radian      !dimensionless
+kilo-       1e3
+k-          kilo
+centi-      1|100
+c-          centi
+gram        millikg
+milli-      1e-3
+m-          milli
+mile        5280 ft      # refers forward
+ft          12 inch
+inch        2.54 cm
+mph         mile/hour
+hour        60 min
+min         60 s
+N           kg m \
+            / s^2
+!locale en_US
+gallon      231 inch^3
+!endlocale
+!locale en_GB
+gallon      4.54609 l
+!endlocale
+";
+
+    #[test]
+    fn load() {
+        let mut registry = UnitRegistry::new();
+        registry.load_units_dat(UNITS).unwrap();
+        assert_eq!(registry.parse("1 km").unwrap().value(), 1e3);
+        assert!((registry.parse("60 mph").unwrap().value() - 26.8224).abs() < 1e-12);
+        assert_eq!(registry.parse("2 grams").unwrap().value(), 2e-3);
+        assert_eq!(registry.parse("1 min").unwrap().value(), 60.0);
+        assert_eq!(
+            registry.parse("1 N").unwrap(),
+            registry.parse("1 kg m/s^2").unwrap()
+        );
+        assert!((registry.parse("gallon").unwrap().value() - 3.785411784e-3).abs() < 1e-15);
+        assert_eq!(registry.parse("2 radians").unwrap().value(), 2.0);
+    }
+
+    #[test]
+    fn errors() {
+        let mut registry = UnitRegistry::new();
+        let errors = registry
+            .load_units_dat(
+                "m !\nbyte !\nfoot 12 inch\n\nyard 3 foot\nF(x) x\n!frobnicate\nmile 1760 yard",
+            )
+            .unwrap_err();
+        let lines: Vec<_> = errors.iter().map(|err| err.line).collect();
+        assert_eq!(lines, [2, 3, 5, 6, 7, 8]);
+        assert_eq!(
+            errors[1].to_string(),
+            "3: can't resolve 'foot': unknown unit 'inch'"
+        );
+        assert_eq!(registry.parse("3 m").unwrap().value(), 3.0);
+    }
+
+    #[test]
+    fn forward_references() {
+        // `mmyft` is defined below, not milli-`myft`
+        let mut registry = UnitRegistry::new();
+        registry
+            .load_units_dat("m !\nm- 1e-3\nmyft 0.3048 m\nx 1 mmyft\nmmyft 7 m")
+            .unwrap();
+        assert_eq!(registry.parse("x").unwrap().value(), 7.0);
+        assert_eq!(registry.parse("2 mmyft").unwrap().value(), 14.0);
+
+        let errors = registry.load_units_dat("a 2 b\nb 3 a\nc 4 mb").unwrap_err();
+        let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "1: can't resolve 'a': 'b' couldn't be resolved",
+                "2: can't resolve 'b': circular definition of 'a'",
+                "3: can't resolve 'c': unknown unit 'mb'",
+            ]
+        );
+    }
+
+    #[test]
+    fn long_chains() {
+        // Digits after a name are an exponent, so the names are spelled in letters
+        let name = |i: u32| -> String {
+            let letter = |digit: u32| char::from(b'a' + (digit % 26) as u8);
+            ['q', letter(i / 676), letter(i / 26), letter(i), 'z']
+                .iter()
+                .collect()
+        };
+        let mut units = String::from("m !\n");
+        for i in 0..6000 {
+            units.push_str(&format!("{} {}\n", name(i), name(i + 1)));
+        }
+        units.push_str(&format!("{} 2 m\n", name(6000)));
+        let mut registry = UnitRegistry::new();
+        registry.load_units_dat(&units).unwrap();
+        assert_eq!(registry.parse(&name(0)).unwrap().value(), 2.0);
+    }
+
+    #[test]
+    fn include_cycles() {
+        let dir = std::env::temp_dir().join(format!("units-dat-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("loop.units"), "m !\n!include other.units\n").unwrap();
+        fs::write(
+            dir.join("other.units"),
+            "ft 0.3048 m\n!include loop.units\n",
+        )
+        .unwrap();
+
+        let mut registry = UnitRegistry::new();
+        let errors = registry
+            .load_units_file(dir.join("loop.units"))
+            .unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 2);
+        assert!(errors[0].message.starts_with("circular !include of"));
+        assert_eq!(registry.parse("1 ft").unwrap().value(), 0.3048);
+    }
+}